@@ -0,0 +1,169 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    mem,
+};
+
+use crate::precedence_graph::{PrecedenceGraph, PrecedenceGraphError};
+
+impl<N: Hash + Eq + Clone, P: Clone> PrecedenceGraph<N, P> {
+    /// Graph with every edge reversed.
+    pub fn dual(&self) -> Self {
+        let mut graph = self.clone();
+
+        for node in &mut graph.nodes {
+            mem::swap(&mut node.succ, &mut node.pred);
+        }
+
+        graph.reachability.take();
+
+        graph
+    }
+
+    /// Graph on the given nodes in which a node reaches another exactly when it does in this graph.
+    ///
+    /// Nodes which are not in the graph are ignored.
+    pub fn induced_subgraph(&self, nodes: &HashSet<N>) -> Self {
+        let reachability = self.reachability();
+        let kept: Vec<usize> = (0..self.nodes.len())
+            .filter(|&node| nodes.contains(&self.nodes[node].val))
+            .collect();
+
+        let mut graph = Self::default();
+
+        for &node in &kept {
+            graph.add_node_with_payload(
+                self.nodes[node].val.clone(),
+                self.nodes[node].payload.clone(),
+            );
+        }
+
+        // Only the closest kept descendants are joined, the rest is implied through them.
+        for (outcome, &node) in kept.iter().enumerate() {
+            let below: Vec<(usize, usize)> = kept
+                .iter()
+                .enumerate()
+                .filter(|&(_, &other)| reachability.reaches(node, other))
+                .map(|(income, &other)| (income, other))
+                .collect();
+
+            for &(income, other) in &below {
+                if !below
+                    .iter()
+                    .any(|&(_, middle)| reachability.reaches(middle, other))
+                {
+                    graph.push_edge(outcome, income);
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Disjoint union of the graphs, fails if they share a node.
+    pub fn parallel_compose(&self, other: &Self) -> Result<Self, PrecedenceGraphError<N>> {
+        Self::disjoint_union([self, other]).map(|(graph, _)| graph)
+    }
+
+    /// Disjoint union in which every sink of this graph precedes every source of the other one,
+    /// fails if the graphs share a node.
+    pub fn series_compose(&self, other: &Self) -> Result<Self, PrecedenceGraphError<N>> {
+        let (mut graph, offsets) = Self::disjoint_union([self, other])?;
+
+        graph.join(self, offsets[0], other, offsets[1]);
+
+        Ok(graph)
+    }
+
+    /// Replaces every node of this graph with its part, so that all nodes of a part precede all
+    /// nodes of another part when the first node precedes the second one here.
+    ///
+    /// Nodes without a part stand for empty parts. Fails if two parts share a node.
+    pub fn lexicographic_sum<M: Hash + Eq + Clone, Q: Clone>(
+        &self,
+        parts: &HashMap<N, PrecedenceGraph<M, Q>>,
+    ) -> Result<PrecedenceGraph<M, Q>, PrecedenceGraphError<M>> {
+        let present: HashSet<N> = self
+            .nodes
+            .iter()
+            .filter(|node| {
+                parts
+                    .get(&node.val)
+                    .is_some_and(|part| part.node_count() > 0)
+            })
+            .map(|node| node.val.clone())
+            .collect();
+
+        // Empty parts are dropped first, so that precedences through them become edges.
+        let outer = self.induced_subgraph(&present);
+        let inner: Vec<&PrecedenceGraph<M, Q>> =
+            outer.nodes.iter().map(|node| &parts[&node.val]).collect();
+
+        let (mut graph, offsets) = PrecedenceGraph::disjoint_union(inner.iter().copied())?;
+
+        for (outcome, node) in outer.nodes.iter().enumerate() {
+            for &income in &node.succ {
+                graph.join(
+                    inner[outcome],
+                    offsets[outcome],
+                    inner[income],
+                    offsets[income],
+                );
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// Copies the graphs into one, returning the index of the first node of each copy.
+    fn disjoint_union<'a>(
+        graphs: impl IntoIterator<Item = &'a Self>,
+    ) -> Result<(Self, Vec<usize>), PrecedenceGraphError<N>>
+    where
+        N: 'a,
+        P: 'a,
+    {
+        let mut graph = Self::default();
+        let mut offsets = vec![];
+
+        for part in graphs {
+            let offset = graph.nodes.len();
+            offsets.push(offset);
+
+            for node in &part.nodes {
+                if !graph.add_node_with_payload(node.val.clone(), node.payload.clone()) {
+                    return Err(PrecedenceGraphError::DuplicateNode {
+                        node: node.val.clone(),
+                    });
+                }
+            }
+
+            for (outcome, node) in part.nodes.iter().enumerate() {
+                for &income in &node.succ {
+                    graph.push_edge(offset + outcome, offset + income);
+                }
+            }
+        }
+
+        Ok((graph, offsets))
+    }
+
+    /// Adds edges from every sink of the first copied graph to every source of the second one.
+    fn join(&mut self, first: &Self, first_offset: usize, second: &Self, second_offset: usize) {
+        for (sink, _) in first
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.succ.is_empty())
+        {
+            for (source, _) in second
+                .nodes
+                .iter()
+                .enumerate()
+                .filter(|(_, node)| node.pred.is_empty())
+            {
+                self.push_edge(first_offset + sink, second_offset + source);
+            }
+        }
+    }
+}
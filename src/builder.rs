@@ -0,0 +1,116 @@
+use std::{collections::HashSet, hash::Hash};
+
+use crate::{
+    precedence_graph::PrecedenceGraph,
+    validation::{ValidatedGraph, ValidationReport},
+};
+
+/// Collects nodes and edges for a [`PrecedenceGraph`].
+///
+/// Edge endpoints have to be declared with [`node`](Self::node) or
+/// [`node_with_payload`](Self::node_with_payload) unless [`auto_nodes`](Self::auto_nodes)
+/// is enabled.
+#[derive(Clone, Debug)]
+pub struct PrecedenceGraphBuilder<N = u128, P = ()> {
+    nodes: Vec<(N, P)>,
+    edges: Vec<(N, N)>,
+    /// Payload of undeclared edge endpoints, `None` if they are rejected.
+    auto_payload: Option<fn() -> P>,
+}
+
+impl<N, P> Default for PrecedenceGraphBuilder<N, P> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            edges: vec![],
+            auto_payload: None,
+        }
+    }
+}
+
+impl<N> PrecedenceGraphBuilder<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraphBuilder<N, P> {
+    /// Declares the node with its payload, the first declaration of a node wins.
+    pub fn node_with_payload(mut self, node: N, payload: P) -> Self {
+        self.nodes.push((node, payload));
+        self
+    }
+
+    pub fn edge(mut self, outcome: N, income: N) -> Self {
+        self.edges.push((outcome, income));
+        self
+    }
+
+    /// Adds edges between every pair of consecutive nodes.
+    pub fn chain(mut self, nodes: &[N]) -> Self {
+        self.edges.extend(
+            nodes
+                .windows(2)
+                .map(|pair| (pair[0].clone(), pair[1].clone())),
+        );
+        self
+    }
+
+    /// Adds edges from `outcome` to each of `incomes`.
+    pub fn fan_out(mut self, outcome: N, incomes: impl IntoIterator<Item = N>) -> Self {
+        self.edges
+            .extend(incomes.into_iter().map(|income| (outcome.clone(), income)));
+        self
+    }
+
+    /// Adds edges from each of `outcomes` to `income`.
+    pub fn fan_in(mut self, outcomes: impl IntoIterator<Item = N>, income: N) -> Self {
+        self.edges.extend(
+            outcomes
+                .into_iter()
+                .map(|outcome| (outcome, income.clone())),
+        );
+        self
+    }
+
+    pub fn extend_edges(mut self, edges: impl IntoIterator<Item = (N, N)>) -> Self {
+        self.edges.extend(edges);
+        self
+    }
+
+    pub fn build(self) -> Result<PrecedenceGraph<N, P>, ValidationReport<N>> {
+        self.build_with_report().map(|(graph, _)| graph)
+    }
+
+    /// Builds the graph, also returning the warnings of its validation.
+    pub fn build_with_report(self) -> ValidatedGraph<N, P> {
+        let endpoints: Vec<(N, P)> = match self.auto_payload {
+            Some(payload) => {
+                let declared: HashSet<&N> = self.nodes.iter().map(|(node, _)| node).collect();
+                let mut added = HashSet::new();
+
+                self.edges
+                    .iter()
+                    .flat_map(|(outcome, income)| [outcome, income])
+                    .filter(|node| !declared.contains(node) && added.insert(*node))
+                    .map(|node| (node.clone(), payload()))
+                    .collect()
+            }
+            None => vec![],
+        };
+
+        PrecedenceGraph::try_build_with_report(self.nodes.into_iter().chain(endpoints), self.edges)
+    }
+}
+
+impl<N: Hash + Eq + Clone, P: Default> PrecedenceGraphBuilder<N, P> {
+    pub fn node(self, node: N) -> Self {
+        self.node_with_payload(node, P::default())
+    }
+
+    /// Adds undeclared edge endpoints with the default payload instead of rejecting them.
+    pub fn auto_nodes(mut self) -> Self {
+        self.auto_payload = Some(P::default);
+        self
+    }
+}
@@ -0,0 +1,41 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::precedence_graph::PrecedenceGraph;
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    /// Undirected graph joining every two different nodes one of which reaches the other.
+    pub fn comparability_graph(&self) -> HashMap<N, HashSet<N>> {
+        self.undirected(|a, b| self.reachability().is_comparable(a, b))
+    }
+
+    /// Undirected graph joining every two nodes neither of which reaches the other.
+    pub fn incomparability_graph(&self) -> HashMap<N, HashSet<N>> {
+        self.undirected(|a, b| !self.reachability().is_comparable(a, b))
+    }
+
+    /// Whether the nodes are different and one of them reaches the other.
+    pub fn is_comparability_edge(&self, a: &N, b: &N) -> bool {
+        a != b && self.is_comparable(a, b)
+    }
+
+    /// Whether both nodes exist and neither of them reaches the other.
+    pub fn is_incomparability_edge(&self, a: &N, b: &N) -> bool {
+        self.contains_node(a) && self.contains_node(b) && !self.is_comparable(a, b)
+    }
+
+    fn undirected(&self, adjacent: impl Fn(usize, usize) -> bool) -> HashMap<N, HashSet<N>> {
+        (0..self.nodes.len())
+            .map(|a| {
+                let neighbours = (0..self.nodes.len())
+                    .filter(|&b| a != b && adjacent(a, b))
+                    .map(|b| self.nodes[b].val.clone())
+                    .collect();
+
+                (self.nodes[a].val.clone(), neighbours)
+            })
+            .collect()
+    }
+}
@@ -1,23 +1,270 @@
+use std::{collections::HashSet, hash::Hash};
+
+/// Creates a [`PrecedenceGraph`](crate::precedence_graph::PrecedenceGraph) from node and edge lists.
+///
+/// Besides `a => b` pairs, edges can be written as chains `1 => 2 => 3`, fan-outs
+/// `1 => [2, 3, 4]` and fan-ins `[1, 2] => 5`.
+///
+/// ```
+/// use precedence_graph::create_graph;
+///
+/// let g = create_graph!(
+///     Nodes: [6],
+///     Edges: [1 => 2 => [3, 4], [3, 4] => 5]
+/// )
+/// .unwrap();
+///
+/// assert!(g.contains_edge(&2, &4));
+/// ```
+///
+/// Nodes in chains and outside of brackets are single tokens, longer expressions are wrapped
+/// in parentheses. Plain `a => b` pairs accept any expressions.
+///
+/// ```
+/// use precedence_graph::create_graph;
+///
+/// let g = create_graph!(
+///     Nodes: [],
+///     Edges: [(2 * 3) => [7, 8] => (-1)]
+/// )
+/// .unwrap();
+///
+/// assert!(g.reaches(&6, &-1));
+/// ```
+///
+/// Duplicate edges and self-loops between literals are rejected at compile time.
+///
+/// ```compile_fail
+/// use precedence_graph::create_graph;
+///
+/// let g = create_graph!(Nodes: [], Edges: [1 => 2, 1 => [2, 3]]);
+/// ```
+///
+/// ```compile_fail
+/// use precedence_graph::create_graph;
+///
+/// let g = create_graph!(Nodes: [], Edges: [1 => 2 => 2]);
+/// ```
 #[macro_export]
-macro_rules! create_graph  {
-    (Nodes:[$($node:expr),*],
-     Edges:[$($outcome:expr => $income:expr),*]) => {{
+macro_rules! create_graph {
+    (Nodes: [$($node:expr),* $(,)?],
+     Edges: [$($($group:tt)=>+),* $(,)?]) => {
+        $crate::create_graph!(@emit [$($node),*] [$([$($group)+])*])
+    };
+
+    (Nodes: [$($node:expr),* $(,)?],
+     Edges: [$($outcome:expr => $income:expr),* $(,)?]) => {
+        $crate::create_graph!(@emit [$($node),*] [$([($outcome) ($income)])*])
+    };
+
+    (@emit [$($node:expr),*] [$([$($group:tt)*])*]) => {{
+        const _: () = {
+            const CHAINS: &[&[&[::std::option::Option<&str>]]] = &[
+                $(&[$($crate::create_graph!(@literals $group)),*]),*
+            ];
+            const TABLE_SIZE: usize = $crate::create_graph::table_size(CHAINS);
+
+            $crate::create_graph::check_literal_edges::<TABLE_SIZE>(CHAINS)
+        };
+
         #[allow(unused_mut)]
-        let mut nodes = HashSet::new();
+        let mut nodes = ::std::collections::HashSet::new();
         #[allow(unused_mut)]
-        let mut edges = HashSet::new();
+        let mut edges = ::std::collections::HashSet::new();
 
         $(
-             nodes.insert($node);
+            nodes.insert($node);
         )*
 
         $(
-            nodes.insert($outcome);
-            nodes.insert($income);
-
-            edges.insert(($outcome, $income));
+            $crate::create_graph::insert_chain(
+                &mut nodes,
+                &mut edges,
+                &[$($crate::create_graph!(@values $group)),*],
+            );
         )*
 
-        PrecedenceGraph::new(nodes, edges)}
+        $crate::precedence_graph::PrecedenceGraph::new(nodes, edges)
+    }};
+
+    (@values [$($nodes:tt)*]) => {
+        ::std::vec![$($nodes)*]
+    };
+    (@values $node:tt) => {
+        ::std::vec![$node]
+    };
+
+    (@literals [$($node:tt),* $(,)?]) => {
+        &[$($crate::create_graph!(@literal $node)),*]
+    };
+    (@literals [$($nodes:tt)*]) => {
+        &[]
+    };
+    (@literals $node:tt) => {
+        &[$crate::create_graph!(@literal $node)]
+    };
+
+    (@literal $node:literal) => {
+        ::std::option::Option::Some(::std::stringify!($node))
     };
+    (@literal $($node:tt)*) => {
+        ::std::option::Option::None
+    };
+}
+
+/// Inserts nodes of every group of the chain and edges between nodes of consecutive groups.
+#[doc(hidden)]
+pub fn insert_chain<N: Hash + Eq + Clone>(
+    nodes: &mut HashSet<N>,
+    edges: &mut HashSet<(N, N)>,
+    chain: &[Vec<N>],
+) {
+    for group in chain {
+        nodes.extend(group.iter().cloned());
+    }
+
+    for pair in chain.windows(2) {
+        for outcome in &pair[0] {
+            for income in &pair[1] {
+                edges.insert((outcome.clone(), income.clone()));
+            }
+        }
+    }
+}
+
+type LiteralChain<'a> = &'a [&'a [Option<&'a str>]];
+
+/// Position of an edge in chains: chain, group, outcome and income indices.
+type EdgeCursor = (usize, usize, usize, usize);
+
+/// Size of the hash table used by [`check_literal_edges`], at least twice the number of edges.
+#[doc(hidden)]
+pub const fn table_size(chains: &[LiteralChain]) -> usize {
+    let mut count: usize = 0;
+    let mut current = first_edge(chains);
+
+    while let Some(edge) = current {
+        count += 1;
+        current = next_edge(chains, edge);
+    }
+
+    (2 * count).next_power_of_two()
+}
+
+/// Fails compilation if two edges between literal nodes coincide or a literal edge is a self-loop.
+///
+/// Nodes are given by their source text, `None` stands for a node which is not a literal.
+/// Literal edges are put into an open addressing hash table of `SIZE` slots, a power of two.
+#[doc(hidden)]
+pub const fn check_literal_edges<const SIZE: usize>(chains: &[LiteralChain]) {
+    let mut table: [Option<EdgeCursor>; SIZE] = [None; SIZE];
+    let mut current = first_edge(chains);
+
+    while let Some(edge) = current {
+        if let (Some(outcome), Some(income)) = edge_nodes(chains, edge) {
+            if str_eq(outcome, income) {
+                panic!("create_graph! contains a self-loop edge");
+            }
+
+            let mut slot = (str_hash(outcome) ^ str_hash(income).rotate_left(32)) as usize % SIZE;
+
+            while let Some(other) = table[slot] {
+                if let (Some(other_outcome), Some(other_income)) = edge_nodes(chains, other) {
+                    if str_eq(outcome, other_outcome) && str_eq(income, other_income) {
+                        panic!("create_graph! contains a duplicate edge");
+                    }
+                }
+
+                slot = (slot + 1) % SIZE;
+            }
+
+            table[slot] = Some(edge);
+        }
+
+        current = next_edge(chains, edge);
+    }
+}
+
+const fn edge_nodes<'a>(
+    chains: &[LiteralChain<'a>],
+    (chain, group, outcome, income): EdgeCursor,
+) -> (Option<&'a str>, Option<&'a str>) {
+    (
+        chains[chain][group][outcome],
+        chains[chain][group + 1][income],
+    )
+}
+
+const fn first_edge(chains: &[LiteralChain]) -> Option<EdgeCursor> {
+    valid_edge(chains, (0, 0, 0, 0))
+}
+
+const fn next_edge(
+    chains: &[LiteralChain],
+    (chain, group, outcome, income): EdgeCursor,
+) -> Option<EdgeCursor> {
+    valid_edge(chains, (chain, group, outcome, income + 1))
+}
+
+/// Moves the cursor forward until it points to an existing edge.
+const fn valid_edge(
+    chains: &[LiteralChain],
+    (mut chain, mut group, mut outcome, mut income): EdgeCursor,
+) -> Option<EdgeCursor> {
+    while chain < chains.len() {
+        let groups = chains[chain];
+
+        if group + 1 >= groups.len() {
+            chain += 1;
+            group = 0;
+            outcome = 0;
+            income = 0;
+        } else if outcome >= groups[group].len() {
+            group += 1;
+            outcome = 0;
+            income = 0;
+        } else if income >= groups[group + 1].len() {
+            outcome += 1;
+            income = 0;
+        } else {
+            return Some((chain, group, outcome, income));
+        }
+    }
+
+    None
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
+/// FNV-1a hash of the string.
+const fn str_hash(s: &str) -> u64 {
+    let bytes = s.as_bytes();
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+        i += 1;
+    }
+
+    hash
 }
@@ -0,0 +1,169 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{node::Node, precedence_graph::PrecedenceGraph};
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    /// Number of nodes in the longest chain.
+    pub fn height(&self) -> usize {
+        self.height_by(|_| 1)
+    }
+
+    /// Longest sequence of nodes each of which has to be done after the previous one.
+    pub fn longest_chain(&self) -> Vec<N> {
+        self.longest_chain_by(|_| 1)
+    }
+
+    /// Total duration of the heaviest chain, durations are taken from payloads.
+    pub fn height_by(&self, duration: impl Fn(&P) -> usize) -> usize {
+        self.finish_times(&duration)
+            .into_iter()
+            .map(|(finish, _)| finish)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Chain with the largest total duration, durations are taken from payloads.
+    pub fn longest_chain_by(&self, duration: impl Fn(&P) -> usize) -> Vec<N> {
+        let finish = self.finish_times(&duration);
+
+        let Some(last) = (0..finish.len()).max_by_key(|&node| finish[node].0) else {
+            return vec![];
+        };
+
+        let mut chain: Vec<usize> =
+            std::iter::successors(Some(last), |&node| finish[node].1).collect();
+        chain.reverse();
+
+        self.values(chain)
+    }
+
+    /// Number of edges in the longest path from a source to the node.
+    pub fn level_from_top(&self, node: &N) -> Option<usize> {
+        let &index = self.indices.get(node)?;
+
+        Some(self.top_levels()[index])
+    }
+
+    /// Number of edges in the longest path from the node to a sink.
+    pub fn level_from_bottom(&self, node: &N) -> Option<usize> {
+        let &index = self.indices.get(node)?;
+
+        Some(self.bottom_levels()[index])
+    }
+
+    /// Total duration of the heaviest chain of predecessors of the node, i.e. its earliest
+    /// start time. Durations are taken from payloads.
+    pub fn level_from_top_by(&self, node: &N, duration: impl Fn(&P) -> usize) -> Option<usize> {
+        let &index = self.indices.get(node)?;
+
+        Some(self.top_levels_by(&duration)[index])
+    }
+
+    /// Total duration of the heaviest chain of successors of the node, durations are taken
+    /// from payloads.
+    pub fn level_from_bottom_by(&self, node: &N, duration: impl Fn(&P) -> usize) -> Option<usize> {
+        let &index = self.indices.get(node)?;
+
+        Some(self.bottom_levels_by(&duration)[index])
+    }
+
+    /// Earliest time step of every node when each node takes one step.
+    pub fn asap_levels(&self) -> HashMap<N, usize> {
+        self.level_map(self.top_levels())
+    }
+
+    /// Latest time step of every node which still finishes within `horizon` steps,
+    /// `None` if the horizon is shorter than the height.
+    pub fn alap_levels(&self, horizon: usize) -> Option<HashMap<N, usize>> {
+        if horizon < self.height() {
+            return None;
+        }
+
+        Some(
+            self.level_map(
+                self.bottom_levels()
+                    .into_iter()
+                    .map(|level| horizon - 1 - level)
+                    .collect(),
+            ),
+        )
+    }
+
+    /// Number of steps every node can be delayed by without exceeding `horizon`,
+    /// `None` if the horizon is shorter than the height.
+    pub fn slack(&self, horizon: usize) -> Option<HashMap<N, usize>> {
+        let mut slack = self.alap_levels(horizon)?;
+
+        for (node, level) in self.asap_levels() {
+            *slack
+                .get_mut(&node)
+                .expect("both maps should have every node") -= level;
+        }
+
+        Some(slack)
+    }
+
+    fn top_levels(&self) -> Vec<usize> {
+        self.top_levels_by(&|_| 1)
+    }
+
+    fn bottom_levels(&self) -> Vec<usize> {
+        self.bottom_levels_by(&|_| 1)
+    }
+
+    fn top_levels_by(&self, duration: &impl Fn(&P) -> usize) -> Vec<usize> {
+        self.levels(self.topological_order(), |node| &node.pred, duration)
+    }
+
+    fn bottom_levels_by(&self, duration: &impl Fn(&P) -> usize) -> Vec<usize> {
+        let mut order = self.topological_order();
+        order.reverse();
+
+        self.levels(order, |node| &node.succ, duration)
+    }
+
+    fn level_map(&self, levels: Vec<usize>) -> HashMap<N, usize> {
+        self.nodes
+            .iter()
+            .map(|node| node.val.clone())
+            .zip(levels)
+            .collect()
+    }
+
+    /// Time each node is finished at if started right after its predecessors, with the predecessor it waits for.
+    fn finish_times(&self, duration: &impl Fn(&P) -> usize) -> Vec<(usize, Option<usize>)> {
+        let mut finish = vec![(0, None); self.nodes.len()];
+
+        for node in self.topological_order() {
+            let (start, critical) = self.nodes[node]
+                .pred
+                .iter()
+                .map(|&pred| (finish[pred].0, Some(pred)))
+                .max_by_key(|&(start, _)| start)
+                .unwrap_or((0, None));
+
+            finish[node] = (start + duration(&self.nodes[node].payload), critical);
+        }
+
+        finish
+    }
+
+    fn levels(
+        &self,
+        order: Vec<usize>,
+        neighbours: impl Fn(&Node<N, P>) -> &Vec<usize>,
+        duration: &impl Fn(&P) -> usize,
+    ) -> Vec<usize> {
+        let mut levels = vec![0; self.nodes.len()];
+
+        for node in order {
+            levels[node] = neighbours(&self.nodes[node])
+                .iter()
+                .map(|&neighbour| levels[neighbour] + duration(&self.nodes[neighbour].payload))
+                .max()
+                .unwrap_or(0);
+        }
+
+        levels
+    }
+}
@@ -0,0 +1,164 @@
+use std::{collections::VecDeque, hash::Hash};
+
+use crate::{precedence_graph::PrecedenceGraph, reachability::Reachability};
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    /// Whether the order is the intersection of at most two linear orders.
+    pub fn is_two_dimensional(&self) -> bool {
+        self.two_dimensional_realizer().is_some()
+    }
+
+    /// Two linear extensions whose intersection is the order, if they exist.
+    ///
+    /// The incomparable pairs are oriented transitively and the first extension follows that
+    /// orientation while the second one reverses it.
+    pub fn two_dimensional_realizer(&self) -> Option<(Vec<N>, Vec<N>)> {
+        let reachability = self.reachability();
+        let oriented = self.orient_incomparable(reachability)?;
+
+        let first = self.total_order(|a, b| reachability.reaches(a, b) || oriented[a][b])?;
+        let second = self.total_order(|a, b| reachability.reaches(a, b) || oriented[b][a])?;
+
+        Some((self.values(first), self.values(second)))
+    }
+
+    /// Size of a realizer found greedily, never less than the dimension of the order.
+    pub fn dimension_upper_bound(&self) -> usize {
+        if self.nodes.is_empty() {
+            return 0;
+        }
+
+        if self.is_linear_order() {
+            return 1;
+        }
+
+        if self.is_two_dimensional() {
+            return 2;
+        }
+
+        self.greedy_realizer_size().min(self.width())
+    }
+
+    /// Transitive orientation of the incomparability graph by Golumbic's G-decomposition.
+    fn orient_incomparable(&self, reachability: &Reachability) -> Option<Vec<Vec<bool>>> {
+        let n = self.nodes.len();
+
+        let mut alive: Vec<Vec<bool>> = (0..n)
+            .map(|a| (0..n).map(|b| !reachability.is_comparable(a, b)).collect())
+            .collect();
+        let mut oriented = vec![vec![false; n]; n];
+
+        for a in 0..n {
+            for b in 0..n {
+                if !alive[a][b] {
+                    continue;
+                }
+
+                // Implication class of the edge in the remaining graph.
+                let mut class = vec![vec![false; n]; n];
+                let mut members = vec![(a, b)];
+                let mut queue = VecDeque::from([(a, b)]);
+                class[a][b] = true;
+
+                while let Some((x, y)) = queue.pop_front() {
+                    let forced = (0..n)
+                        .filter(|&z| z != y && alive[x][z] && !alive[y][z])
+                        .map(|z| (x, z))
+                        .chain(
+                            (0..n)
+                                .filter(|&z| z != x && alive[z][y] && !alive[x][z])
+                                .map(|z| (z, y)),
+                        )
+                        .collect::<Vec<_>>();
+
+                    for (u, v) in forced {
+                        if class[v][u] {
+                            return None;
+                        }
+
+                        if !class[u][v] {
+                            class[u][v] = true;
+                            members.push((u, v));
+                            queue.push_back((u, v));
+                        }
+                    }
+                }
+
+                for (u, v) in members {
+                    oriented[u][v] = true;
+                    alive[u][v] = false;
+                    alive[v][u] = false;
+                }
+            }
+        }
+
+        Some(oriented)
+    }
+
+    /// Nodes sorted by the relation, `None` if it is not a strict linear order.
+    fn total_order(&self, less: impl Fn(usize, usize) -> bool) -> Option<Vec<usize>> {
+        let n = self.nodes.len();
+        let mut order = vec![None; n];
+
+        // A tournament is transitive exactly when its nodes have pairwise different scores.
+        for b in 0..n {
+            let rank = (0..n).filter(|&a| less(a, b)).count();
+
+            if order[rank].replace(b).is_some() {
+                return None;
+            }
+        }
+
+        order.into_iter().collect()
+    }
+
+    /// Number of linear extensions built until every incomparable pair is reversed in one of them.
+    fn greedy_realizer_size(&self) -> usize {
+        let reachability = self.reachability();
+        let n = self.nodes.len();
+        let words = n.div_ceil(64);
+        let contains = |row: &[u64], node: usize| row[node / 64] & (1 << (node % 64)) != 0;
+
+        // Ordered incomparable pairs `(a, b)` still waiting for an extension with `b` before `a`.
+        let mut uncovered: Vec<(usize, usize)> = (0..n)
+            .flat_map(|a| (0..n).map(move |b| (a, b)))
+            .filter(|&(a, b)| !reachability.is_comparable(a, b))
+            .collect();
+        let mut size = 0;
+
+        while !uncovered.is_empty() {
+            let mut relation: Vec<Vec<u64>> = (0..n)
+                .map(|node| {
+                    let mut row = vec![0; words];
+                    reachability
+                        .descendants(node)
+                        .iter()
+                        .for_each(|desc| row[desc / 64] |= 1 << (desc % 64));
+                    row
+                })
+                .collect();
+
+            for &(a, b) in &uncovered {
+                if contains(&relation[a], b) || contains(&relation[b], a) {
+                    continue;
+                }
+
+                let mut above = relation[a].clone();
+                above[a / 64] |= 1 << (a % 64);
+
+                for (x, row) in relation.iter_mut().enumerate() {
+                    if x == b || contains(row, b) {
+                        for (word, extra) in row.iter_mut().zip(&above) {
+                            *word |= extra;
+                        }
+                    }
+                }
+            }
+
+            uncovered.retain(|&(a, b)| !contains(&relation[b], a));
+            size += 1;
+        }
+
+        size
+    }
+}
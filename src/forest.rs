@@ -0,0 +1,93 @@
+use std::hash::Hash;
+
+use crate::precedence_graph::PrecedenceGraph;
+
+/// Numbers of immediate successors and predecessors of every node, ignoring redundant edges.
+struct Covers {
+    upper: Vec<usize>,
+    lower: Vec<usize>,
+}
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    /// Whether every node has at most one immediate successor.
+    pub fn is_in_forest(&self) -> bool {
+        self.covers().upper.iter().all(|&count| count <= 1)
+    }
+
+    /// Whether every node has at most one immediate predecessor.
+    pub fn is_out_forest(&self) -> bool {
+        self.covers().lower.iter().all(|&count| count <= 1)
+    }
+
+    /// Whether every weakly connected component is an in-tree or an out-tree.
+    pub fn is_opposing_forest(&self) -> bool {
+        let covers = self.covers();
+
+        self.weak_components().iter().all(|component| {
+            component.iter().all(|&node| covers.upper[node] <= 1)
+                || component.iter().all(|&node| covers.lower[node] <= 1)
+        })
+    }
+
+    /// Whether every weakly connected component is a chain.
+    pub fn is_chain_union(&self) -> bool {
+        let covers = self.covers();
+
+        (0..self.nodes.len()).all(|node| covers.upper[node] <= 1 && covers.lower[node] <= 1)
+    }
+
+    /// Roots of the in-trees, `None` if the graph is not an in-forest.
+    pub fn in_forest_roots(&self) -> Option<Vec<N>> {
+        self.is_in_forest().then(|| self.sinks().cloned().collect())
+    }
+
+    /// Roots of the out-trees, `None` if the graph is not an out-forest.
+    pub fn out_forest_roots(&self) -> Option<Vec<N>> {
+        self.is_out_forest()
+            .then(|| self.sources().cloned().collect())
+    }
+
+    fn covers(&self) -> Covers {
+        let mut covers = Covers {
+            upper: self.nodes.iter().map(|node| node.succ.len()).collect(),
+            lower: self.nodes.iter().map(|node| node.pred.len()).collect(),
+        };
+
+        for (outcome, income) in self.redundant_edge_indices() {
+            covers.upper[outcome] -= 1;
+            covers.lower[income] -= 1;
+        }
+
+        covers
+    }
+
+    fn weak_components(&self) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.nodes.len()];
+        let mut components = vec![];
+
+        for start in 0..self.nodes.len() {
+            if visited[start] {
+                continue;
+            }
+
+            visited[start] = true;
+            let mut stack = vec![start];
+            let mut component = vec![];
+
+            while let Some(node) = stack.pop() {
+                component.push(node);
+
+                for &next in self.nodes[node].succ.iter().chain(&self.nodes[node].pred) {
+                    if !visited[next] {
+                        visited[next] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components
+    }
+}
@@ -0,0 +1,90 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::precedence_graph::PrecedenceGraph;
+
+/// Closed range of integer points assigned to a node.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Interval {
+    pub left: usize,
+    pub right: usize,
+}
+
+/// Two chains `a < b` and `c < d` with no other precedence between their nodes.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TwoPlusTwo<N = u128> {
+    pub a: N,
+    pub b: N,
+    pub c: N,
+    pub d: N,
+}
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    /// Whether the graph contains no [`TwoPlusTwo`].
+    pub fn is_interval_order(&self) -> bool {
+        self.interval_representation().is_ok()
+    }
+
+    /// Assigns intervals to nodes so that `a` precedes `b` exactly when the interval
+    /// of `a` ends before the interval of `b` starts.
+    pub fn interval_representation(&self) -> Result<HashMap<N, Interval>, TwoPlusTwo<N>> {
+        let reachability = self.reachability();
+
+        let mut order: Vec<usize> = (0..self.nodes.len()).collect();
+        order.sort_by_key(|&node| reachability.ancestors(node).len());
+
+        // Interval orders are exactly the orders whose sets of predecessors form a chain.
+        let mut left = vec![0; self.nodes.len()];
+        let mut current = 0;
+
+        for pair in order.windows(2) {
+            let (x, y) = (pair[0], pair[1]);
+            let (pred_x, pred_y) = (reachability.ancestors(x), reachability.ancestors(y));
+
+            if !pred_x.is_subset(pred_y) {
+                let a = pred_x
+                    .difference(pred_y)
+                    .next()
+                    .expect("difference should not be empty");
+                let c = pred_y
+                    .difference(pred_x)
+                    .next()
+                    .expect("sets of the same size should differ both ways");
+
+                return Err(TwoPlusTwo {
+                    a: self.nodes[a].val.clone(),
+                    b: self.nodes[x].val.clone(),
+                    c: self.nodes[c].val.clone(),
+                    d: self.nodes[y].val.clone(),
+                });
+            }
+
+            if !pred_y.is_subset(pred_x) {
+                current += 1;
+            }
+
+            left[y] = current;
+        }
+
+        Ok(self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let right = node
+                    .succ
+                    .iter()
+                    .map(|&succ| left[succ] - 1)
+                    .min()
+                    .unwrap_or(current);
+
+                (
+                    node.val.clone(),
+                    Interval {
+                        left: left[index],
+                        right,
+                    },
+                )
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,319 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::precedence_graph::PrecedenceGraph;
+
+/// Immediate precedences of a graph between canonical positions of its nodes, equal for
+/// isomorphic graphs regardless of node ids and payloads.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct CanonicalForm {
+    pub node_count: usize,
+    /// Sorted pairs of positions, the first one immediately precedes the second one.
+    pub edges: Vec<(usize, usize)>,
+}
+
+/// Leaf of the search tree: cover pairs under its labelling, the labelling and the path to it.
+#[derive(Clone)]
+struct Leaf {
+    edges: Vec<(usize, usize)>,
+    labelling: Vec<usize>,
+    path: Vec<usize>,
+}
+
+/// State of the individualisation-refinement search for the smallest labelling.
+struct Search {
+    covers: Vec<(usize, usize)>,
+    /// Immediate successors and predecessors of every node.
+    upper: Vec<Vec<usize>>,
+    lower: Vec<Vec<usize>>,
+    /// Smallest node with the same immediate successors and predecessors, swapping such
+    /// twins is an automorphism.
+    twin: Vec<usize>,
+    /// Automorphisms found from pairs of equal leaves.
+    automorphisms: Vec<Vec<usize>>,
+    first: Option<Leaf>,
+    best: Option<Leaf>,
+}
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    /// Bijection between nodes which preserves the order in both directions, if one exists.
+    pub fn is_isomorphic<M: Hash + Eq + Clone, Q>(
+        &self,
+        other: &PrecedenceGraph<M, Q>,
+    ) -> Option<HashMap<N, M>> {
+        let (form, labelling) = self.canonical_labelling();
+        let (other_form, other_labelling) = other.canonical_labelling();
+
+        if form != other_form {
+            return None;
+        }
+
+        let mut at_position = vec![0; other_labelling.len()];
+
+        for (node, &position) in other_labelling.iter().enumerate() {
+            at_position[position] = node;
+        }
+
+        Some(
+            labelling
+                .iter()
+                .enumerate()
+                .map(|(node, &position)| {
+                    (
+                        self.nodes[node].val.clone(),
+                        other.nodes[at_position[position]].val.clone(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Encoding of the order which does not depend on node ids.
+    pub fn canonical_form(&self) -> CanonicalForm {
+        self.canonical_labelling().0
+    }
+
+    /// Canonical form with the position of every node in it.
+    fn canonical_labelling(&self) -> (CanonicalForm, Vec<usize>) {
+        let redundant: HashSet<(usize, usize)> =
+            self.redundant_edge_indices().into_iter().collect();
+        let covers: Vec<(usize, usize)> = self
+            .edges_by_index()
+            .filter(|edge| !redundant.contains(edge))
+            .collect();
+
+        let mut upper = vec![vec![]; self.nodes.len()];
+        let mut lower = vec![vec![]; self.nodes.len()];
+
+        for &(outcome, income) in &covers {
+            upper[outcome].push(income);
+            lower[income].push(outcome);
+        }
+
+        for neighbours in upper.iter_mut().chain(&mut lower) {
+            neighbours.sort_unstable();
+        }
+
+        let mut representatives = HashMap::new();
+        let twin = (0..self.nodes.len())
+            .map(|node| {
+                *representatives
+                    .entry((&upper[node], &lower[node]))
+                    .or_insert(node)
+            })
+            .collect();
+
+        let mut search = Search {
+            covers,
+            upper,
+            lower,
+            twin,
+            automorphisms: vec![],
+            first: None,
+            best: None,
+        };
+
+        let colours = search.refine(vec![0; self.nodes.len()]);
+        search.visit(colours, &mut vec![]);
+
+        let best = search.best.expect("search should reach a leaf");
+
+        (
+            CanonicalForm {
+                node_count: self.nodes.len(),
+                edges: best.edges,
+            },
+            best.labelling,
+        )
+    }
+
+    fn edges_by_index(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .flat_map(|(outcome, node)| node.succ.iter().map(move |&income| (outcome, income)))
+    }
+}
+
+impl Search {
+    /// Explores the subtree of the partition, returning the depth to backtrack to when the
+    /// subtree turned out to mirror an explored one.
+    fn visit(&mut self, colours: Vec<usize>, path: &mut Vec<usize>) -> Option<usize> {
+        let mut cells: Vec<Vec<usize>> = vec![vec![]; colours.len()];
+
+        for (node, &colour) in colours.iter().enumerate() {
+            cells[colour].push(node);
+        }
+
+        let Some(cell) = cells.into_iter().find(|cell| cell.len() > 1) else {
+            return self.leaf(colours, path);
+        };
+
+        let depth = path.len();
+        let mut tried: Vec<usize> = vec![];
+
+        for &node in &cell {
+            let orbits = self.orbits(path);
+
+            if tried
+                .iter()
+                .any(|&other| self.twin[other] == self.twin[node] || orbits[other] == orbits[node])
+            {
+                continue;
+            }
+
+            tried.push(node);
+            path.push(node);
+            let backtrack = self.visit(self.individualise(&colours, node), path);
+            path.pop();
+
+            match backtrack {
+                Some(level) if level < depth => return Some(level),
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    /// Representative of the orbit of every node under the found automorphisms which fix
+    /// the path.
+    fn orbits(&self, path: &[usize]) -> Vec<usize> {
+        let mut orbits: Vec<usize> = (0..self.twin.len()).collect();
+
+        fn find(orbits: &mut [usize], mut node: usize) -> usize {
+            while orbits[node] != node {
+                orbits[node] = orbits[orbits[node]];
+                node = orbits[node];
+            }
+
+            node
+        }
+
+        for automorphism in &self.automorphisms {
+            if path.iter().any(|&node| automorphism[node] != node) {
+                continue;
+            }
+
+            for (node, &image) in automorphism.iter().enumerate() {
+                let (a, b) = (find(&mut orbits, node), find(&mut orbits, image));
+                orbits[a.max(b)] = a.min(b);
+            }
+        }
+
+        (0..orbits.len())
+            .map(|node| find(&mut orbits, node))
+            .collect()
+    }
+
+    fn leaf(&mut self, labelling: Vec<usize>, path: &[usize]) -> Option<usize> {
+        let mut edges: Vec<(usize, usize)> = self
+            .covers
+            .iter()
+            .map(|&(outcome, income)| (labelling[outcome], labelling[income]))
+            .collect();
+        edges.sort_unstable();
+
+        // Equal leaves differ by an automorphism, so the subtree since the paths diverged
+        // contains nothing new.
+        for leaf in [&self.first, &self.best].into_iter().flatten() {
+            if leaf.edges == edges {
+                let mut at_position = vec![0; labelling.len()];
+
+                for (node, &position) in leaf.labelling.iter().enumerate() {
+                    at_position[position] = node;
+                }
+
+                let backtrack = common_prefix(&leaf.path, path);
+                self.automorphisms.push(
+                    labelling
+                        .iter()
+                        .map(|&position| at_position[position])
+                        .collect(),
+                );
+
+                return Some(backtrack);
+            }
+        }
+
+        let leaf = Leaf {
+            edges,
+            labelling,
+            path: path.to_vec(),
+        };
+
+        if self.first.is_none() {
+            self.first = Some(leaf.clone());
+        }
+
+        let improves = match &self.best {
+            Some(best) => leaf.edges < best.edges,
+            None => true,
+        };
+
+        if improves {
+            self.best = Some(leaf);
+        }
+
+        None
+    }
+
+    /// Gives the node a colour of its own, placed right before the rest of its cell.
+    fn individualise(&self, colours: &[usize], node: usize) -> Vec<usize> {
+        let split = colours
+            .iter()
+            .enumerate()
+            .map(|(other, &colour)| {
+                2 * colour + usize::from(colour == colours[node] && other != node)
+            })
+            .collect();
+
+        self.refine(split)
+    }
+
+    /// Splits colours by the colours of immediate neighbours until nothing changes,
+    /// renumbering them from zero in an order which does not depend on node indices.
+    fn refine(&self, mut colours: Vec<usize>) -> Vec<usize> {
+        let mut count = usize::MAX;
+
+        loop {
+            let signatures: Vec<(usize, Vec<usize>, Vec<usize>)> = (0..colours.len())
+                .map(|node| {
+                    let mut below: Vec<usize> =
+                        self.upper[node].iter().map(|&succ| colours[succ]).collect();
+                    let mut above: Vec<usize> =
+                        self.lower[node].iter().map(|&pred| colours[pred]).collect();
+                    below.sort_unstable();
+                    above.sort_unstable();
+
+                    (colours[node], below, above)
+                })
+                .collect();
+
+            let mut distinct: Vec<&(usize, Vec<usize>, Vec<usize>)> = signatures.iter().collect();
+            distinct.sort_unstable();
+            distinct.dedup();
+
+            colours = signatures
+                .iter()
+                .map(|signature| {
+                    distinct
+                        .binary_search(&signature)
+                        .expect("signature should be among distinct ones")
+                })
+                .collect();
+
+            if distinct.len() == count {
+                return colours;
+            }
+
+            count = distinct.len();
+        }
+    }
+}
+
+fn common_prefix(a: &[usize], b: &[usize]) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
@@ -1,14 +1,30 @@
+mod algebra;
+pub mod builder;
+mod comparability;
 pub mod create_graph;
+mod critical_path;
+mod dimension;
+mod forest;
+pub mod interval;
+pub mod isomorphism;
+mod linear_extensions;
 mod node;
+pub mod order_diff;
 pub mod precedence_graph;
+pub mod quasi_interval;
+mod reachability;
+pub mod series_parallel;
+mod transitive;
+pub mod validation;
+mod width;
 
 #[cfg(test)]
+#[allow(clippy::manual_range_patterns)]
 mod tests {
-    use crate::precedence_graph::PrecedenceGraph;
+    use crate::precedence_graph::{PrecedenceGraph, PrecedenceGraphError};
     use std::collections::HashSet;
 
     mod quasi_interval_order {
-        use super::*;
         use crate::create_graph;
 
         #[test]
@@ -186,377 +202,1299 @@ mod tests {
         }
     }
 
-    mod linear_order {
-        use super::*;
-        use crate::create_graph;
+    mod quasi_interval_violations {
+        use crate::{create_graph, quasi_interval::QuasiIntervalViolation};
 
         #[test]
-        fn is_linear_order() {
+        fn first_violation() {
             let g = create_graph!(
                 Nodes: [],
-                Edges: [1 => 2, 2 => 3]
+                Edges: [1 => [2, 3], 4 => 5]
             )
             .unwrap();
 
-            assert!(g.is_linear_order());
+            assert!(matches!(
+                g.first_quasi_interval_violation(),
+                Some(QuasiIntervalViolation::First {
+                    i: 1,
+                    j: 4,
+                    x: 2 | 3,
+                    y: 2 | 3
+                })
+            ));
 
             let g = create_graph!(
                 Nodes: [],
-                Edges: []
+                Edges: [1 => 3 => 5, 2 => 3, 4 => 6 => 5]
             )
             .unwrap();
 
-            assert!(g.is_linear_order());
+            let Some(QuasiIntervalViolation::Second { i, j, x, y, z }) =
+                g.first_quasi_interval_violation()
+            else {
+                panic!("expected violation of the second kind");
+            };
+
+            assert!(g.reaches(&i, &x) && !g.reaches(&j, &x));
+            assert!(g.reaches(&j, &z) && !g.reaches(&i, &z));
+            assert!(g.reaches(&y, &x) && !g.reaches(&y, &z));
+            assert!(!g.is_comparable(&i, &y));
         }
 
         #[test]
-        fn is_not_linear_order() {
+        fn all_violations() {
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => [2, 3], 4 => 5 => 3, 4 => 3]
+            )
+            .unwrap();
+
+            let violations = g.quasi_interval_violations();
+
+            let third: Vec<_> = violations
+                .iter()
+                .filter_map(|violation| match violation {
+                    QuasiIntervalViolation::Third { i, j, x, y, z } => Some((i, j, x, y, z)),
+                    _ => None,
+                })
+                .collect();
+
+            assert!(!third.is_empty());
+
+            for (i, j, x, y, z) in third {
+                assert!(g.reaches(i, x) && !g.reaches(j, x));
+                assert!(g.reaches(j, z) && !g.reaches(i, z));
+                assert!(g.reaches(x, y) && g.reaches(j, y) && !g.reaches(z, y));
+            }
+
             let g = create_graph!(
                 Nodes: [],
                 Edges: [1 => 2, 4 => 5]
             )
             .unwrap();
 
-            assert!(!g.is_linear_order());
+            assert!(g.quasi_interval_violations().is_empty());
+            assert_eq!(g.first_quasi_interval_violation(), None);
         }
     }
 
-    mod msf {
-        use super::*;
-        use crate::create_graph;
+    mod interval_order {
+        use crate::{create_graph, interval::TwoPlusTwo};
 
         #[test]
-        fn general() {
+        fn representation() {
             let g = create_graph!(
-                Nodes: [],
-                Edges: [5 => 1, 5 => 2, 5 => 3, 7 => 6, 6 => 4]
+                Nodes: [6],
+                Edges: [[1, 2] => 3 => 5, 4 => 5, 1 => 4]
             )
             .unwrap();
 
-            let res = g.msf_list();
+            assert!(g.is_interval_order());
 
-            assert!(matches!(res[0], 5));
-            assert!(matches!(res[1], 7));
-            assert!(matches!(res[2], 6));
-            assert!(matches!(res[3], 1 | 2 | 3 | 4));
-            assert!(matches!(res[4], 1 | 2 | 3 | 4));
-            assert!(matches!(res[5], 1 | 2 | 3 | 4));
-            assert!(matches!(res[6], 1 | 2 | 3 | 4));
+            let intervals = g.interval_representation().unwrap();
+
+            assert_eq!(intervals.len(), 6);
+
+            for a in g.nodes() {
+                assert!(intervals[a].left <= intervals[a].right);
+
+                for b in g.nodes() {
+                    assert_eq!(
+                        g.reaches(a, b),
+                        intervals[a].right < intervals[b].left,
+                        "{a} and {b}"
+                    );
+                }
+            }
+        }
 
+        #[test]
+        fn two_plus_two() {
             let g = create_graph!(
                 Nodes: [],
-                Edges: [8 => 5, 5 => 1, 5 => 2, 5 => 3, 7 => 6, 6 => 4]
+                Edges: [1 => 2, 3 => 4, 1 => 5]
             )
             .unwrap();
 
-            let res = g.msf_list();
+            assert!(!g.is_interval_order());
 
-            assert!(matches!(res[0], 8));
-            assert!(matches!(res[1], 5 | 7));
-            assert!(matches!(res[2], 5 | 7));
-            assert!(matches!(res[3], 6));
-            assert!(matches!(res[4], 1 | 2 | 3 | 4));
-            assert!(matches!(res[5], 1 | 2 | 3 | 4));
-            assert!(matches!(res[6], 1 | 2 | 3 | 4));
-            assert!(matches!(res[7], 1 | 2 | 3 | 4));
+            let TwoPlusTwo { a, b, c, d } = g.interval_representation().unwrap_err();
+
+            assert!(g.reaches(&a, &b) && g.reaches(&c, &d));
+
+            for (x, y) in [(a, c), (a, d), (b, c), (b, d)] {
+                assert!(!g.is_comparable(&x, &y));
+            }
+        }
+    }
 
+    mod series_parallel {
+        use crate::{
+            create_graph,
+            precedence_graph::PrecedenceGraph,
+            series_parallel::{NShape, SeriesParallel},
+        };
+
+        fn leaves(tree: &SeriesParallel<i32>) -> Vec<i32> {
+            match tree {
+                SeriesParallel::Leaf(node) => vec![*node],
+                SeriesParallel::Series(a, b) | SeriesParallel::Parallel(a, b) => {
+                    [leaves(a), leaves(b)].concat()
+                }
+            }
+        }
+
+        #[test]
+        fn decomposition() {
             let g = create_graph!(
                 Nodes: [],
-                Edges: [1 => 3, 2 => 3, 3 => 5, 4 => 6, 6 => 5]
+                Edges: [1 => [2, 3] => 4]
             )
             .unwrap();
 
-            let res = g.msf_list();
+            assert!(g.is_series_parallel());
 
-            assert!(matches!(res[0], 1 | 2 | 4));
-            assert!(matches!(res[1], 1 | 2 | 4));
-            assert!(matches!(res[2], 1 | 2 | 4));
-            assert!(matches!(res[3], 3 | 6));
-            assert!(matches!(res[4], 3 | 6));
-            assert!(matches!(res[5], 5));
+            let tree = g.series_parallel_decomposition().unwrap().unwrap();
+
+            let SeriesParallel::Series(rest, last) = &tree else {
+                panic!("expected series composition, got {tree:?}");
+            };
+
+            assert_eq!(**last, SeriesParallel::Leaf(4));
+
+            let SeriesParallel::Series(first, middle) = &**rest else {
+                panic!("expected series composition, got {rest:?}");
+            };
+
+            assert_eq!(**first, SeriesParallel::Leaf(1));
+            assert!(matches!(**middle, SeriesParallel::Parallel(_, _)));
+
+            let mut nodes = leaves(&tree);
+            nodes.sort();
+            assert_eq!(nodes, vec![1, 2, 3, 4]);
 
+            let g: PrecedenceGraph = create_graph!(Nodes: [], Edges: []).unwrap();
+
+            assert_eq!(g.series_parallel_decomposition(), Ok(None));
+        }
+
+        #[test]
+        fn n_shape() {
             let g = create_graph!(
-                Nodes: [],
-                Edges: [1 => 3, 1 => 5, 2 => 3, 2 => 6, 5 => 7, 6 => 7, 3 => 8, 7 => 9, 8 => 9]
+                Nodes: [5],
+                Edges: [1 => 3, 2 => [3, 4]]
             )
             .unwrap();
 
-            let res = g.msf_list();
+            assert!(!g.is_series_parallel());
+            assert_eq!(
+                g.series_parallel_decomposition(),
+                Err(NShape {
+                    a: 1,
+                    b: 2,
+                    c: 3,
+                    d: 4
+                })
+            );
+        }
+    }
 
-            assert!(matches!(res[0], 1 | 2));
-            assert!(matches!(res[1], 1 | 2));
-            assert!(matches!(res[2], 3 | 5 | 6));
-            assert!(matches!(res[3], 3 | 5 | 6));
-            assert!(matches!(res[4], 3 | 5 | 6));
-            assert!(matches!(res[5], 7 | 8));
-            assert!(matches!(res[6], 7 | 8));
-            assert!(matches!(res[7], 9));
+    mod width {
+        use super::*;
+        use crate::create_graph;
 
+        #[test]
+        fn antichain_and_chains() {
             let g = create_graph!(
-                Nodes: [],
-                Edges: [1 => 2, 1 => 3, 2 => 4, 4 => 7, 7 => 8, 3 => 5, 3 => 6, 5 => 8, 6 => 8]
+                Nodes: [7],
+                Edges: [1 => [2, 3, 4] => 5 => 6, 2 => 6]
             )
             .unwrap();
 
-            let res = g.msf_list();
+            assert_eq!(g.width(), 4);
+
+            let antichain = g.maximum_antichain();
+            assert_eq!(antichain.len(), 4);
+            for a in &antichain {
+                for b in &antichain {
+                    assert!(a == b || !g.is_comparable(a, b));
+                }
+            }
+
+            let chains = g.minimum_chain_partition();
+            assert_eq!(chains.len(), 4);
+            for chain in &chains {
+                assert!(chain.windows(2).all(|pair| g.reaches(&pair[0], &pair[1])));
+            }
+            assert_eq!(
+                chains.iter().flatten().copied().collect::<HashSet<_>>(),
+                HashSet::from([1, 2, 3, 4, 5, 6, 7])
+            );
+            assert_eq!(chains.iter().map(Vec::len).sum::<usize>(), 7);
+        }
 
-            assert!(matches!(res[0], 1));
-            assert!(matches!(res[1], 2 | 3));
-            assert!(matches!(res[2], 2 | 3));
-            assert!(matches!(res[3], 4));
-            assert!(matches!(res[4], 5 | 6 | 7));
-            assert!(matches!(res[5], 5 | 6 | 7));
-            assert!(matches!(res[6], 5 | 6 | 7));
-            assert!(matches!(res[7], 8));
+        #[test]
+        fn linear_and_empty() {
+            let g = create_graph!(Nodes: [], Edges: [1 => 2 => 3]).unwrap();
+
+            assert_eq!(g.width(), 1);
+            assert_eq!(g.minimum_chain_partition(), vec![vec![1, 2, 3]]);
+
+            let g: PrecedenceGraph = create_graph!(Nodes: [], Edges: []).unwrap();
+
+            assert_eq!(g.width(), 0);
+            assert!(g.maximum_antichain().is_empty());
+        }
+    }
+
+    mod critical_path {
+        use super::*;
+        use crate::create_graph;
+        use std::collections::HashMap;
 
+        #[test]
+        fn unit_times() {
             let g = create_graph!(
-                Nodes: [],
-                Edges: [1 => 2, 1 => 3, 2 => 4, 4 => 7, 7 => 10, 3 => 5, 5 => 6, 6 => 8, 6 => 9, 8 => 10, 9 => 10]
+                Nodes: [6],
+                Edges: [1 => 2 => 3 => 4, 1 => 5 => 4]
             )
             .unwrap();
 
-            let res = g.msf_list();
+            assert_eq!(g.height(), 4);
+            assert_eq!(g.longest_chain(), vec![1, 2, 3, 4]);
 
-            assert!(matches!(res[0], 1));
-            assert!(matches!(res[1], 3));
-            assert!(matches!(res[2], 5));
-            assert!(matches!(res[3], 2 | 6));
-            assert!(matches!(res[4], 2 | 6));
-            assert!(matches!(res[5], 4));
-            assert!(matches!(res[6], 7 | 8 | 9));
-            assert!(matches!(res[7], 7 | 8 | 9));
-            assert!(matches!(res[8], 7 | 8 | 9));
-            assert!(matches!(res[9], 10));
+            assert_eq!(g.level_from_top(&5), Some(1));
+            assert_eq!(g.level_from_top(&4), Some(3));
+            assert_eq!(g.level_from_bottom(&1), Some(3));
+            assert_eq!(g.level_from_bottom(&5), Some(1));
+            assert_eq!(g.level_from_bottom(&6), Some(0));
+            assert_eq!(g.level_from_top(&7), None);
+
+            let g: PrecedenceGraph = create_graph!(Nodes: [], Edges: []).unwrap();
+
+            assert_eq!(g.height(), 0);
+            assert!(g.longest_chain().is_empty());
         }
 
         #[test]
-        fn quasi_interval_order() {
+        fn asap_alap_and_slack() {
             let g = create_graph!(
-                Nodes: [],
-                Edges: [1 => 2, 4 => 5]
+                Nodes: [6],
+                Edges: [1 => 2 => 3 => 4, 1 => 5 => 4]
             )
             .unwrap();
 
-            let res = g.msf_list();
+            assert_eq!(
+                g.asap_levels(),
+                HashMap::from([(1, 0), (2, 1), (3, 2), (4, 3), (5, 1), (6, 0)])
+            );
+            assert_eq!(
+                g.alap_levels(5).unwrap(),
+                HashMap::from([(1, 1), (2, 2), (3, 3), (4, 4), (5, 3), (6, 4)])
+            );
+            assert_eq!(
+                g.slack(4).unwrap(),
+                HashMap::from([(1, 0), (2, 0), (3, 0), (4, 0), (5, 1), (6, 3)])
+            );
+            assert_eq!(g.alap_levels(3), None);
+        }
 
-            assert!(matches!(res[0], 1 | 4));
-            assert!(matches!(res[1], 1 | 4));
-            assert!(matches!(res[2], 2 | 5));
-            assert!(matches!(res[2], 2 | 5));
+        #[test]
+        fn durations() {
+            let g = PrecedenceGraph::with_payloads(
+                HashMap::from([(1, 1), (2, 1), (3, 1), (4, 1), (5, 10)]),
+                HashSet::from([(1, 2), (2, 3), (3, 4), (1, 5), (5, 4)]),
+            )
+            .unwrap();
+
+            assert_eq!(g.height_by(|&duration| duration), 12);
+            assert_eq!(g.longest_chain_by(|&duration| duration), vec![1, 5, 4]);
+            assert_eq!(g.longest_chain(), vec![1, 2, 3, 4]);
+
+            assert_eq!(g.level_from_top_by(&4, |&duration| duration), Some(11));
+            assert_eq!(g.level_from_top_by(&3, |&duration| duration), Some(2));
+            assert_eq!(g.level_from_bottom_by(&1, |&duration| duration), Some(11));
+            assert_eq!(g.level_from_bottom_by(&2, |&duration| duration), Some(2));
+            assert_eq!(g.level_from_top_by(&4, |_| 1), g.level_from_top(&4));
+            assert_eq!(g.level_from_bottom_by(&6, |&duration| duration), None);
+        }
+    }
+
+    mod linear_extensions {
+        use super::*;
+        use crate::create_graph;
+        use rand::{rngs::StdRng, SeedableRng};
 
+        fn respects_edges(g: &PrecedenceGraph<i32>, order: &[i32]) -> bool {
+            g.edges().all(|(a, b)| {
+                order.iter().position(|node| node == a) < order.iter().position(|node| node == b)
+            })
+        }
+
+        #[test]
+        fn enumeration_and_count() {
             let g = create_graph!(
                 Nodes: [],
-                Edges: [1 => 4, 1 => 6, 2 => 4, 2 => 5, 2 => 6, 3 => 6, 5 => 7, 6 => 7]
+                Edges: [1 => [2, 3] => 4, 3 => 5]
             )
             .unwrap();
 
-            let res = g.msf_list();
+            let extensions: Vec<Vec<i32>> = g.linear_extensions().collect();
 
-            assert!(matches!(res[0], 2));
-            assert!(matches!(res[1], 1));
-            assert!(matches!(res[2], 3));
-            assert!(matches!(res[3], 5 | 6));
-            assert!(matches!(res[4], 5 | 6));
-            assert!(matches!(res[5], 4 | 7));
-            assert!(matches!(res[6], 4 | 7));
+            assert_eq!(extensions.len(), 5);
+            assert_eq!(extensions.iter().collect::<HashSet<_>>().len(), 5);
+            for extension in &extensions {
+                assert_eq!(extension[0], 1);
+                assert!(respects_edges(&g, extension));
+            }
+            assert_eq!(g.count_linear_extensions(), Some(5));
 
+            let g: PrecedenceGraph = create_graph!(Nodes: [], Edges: []).unwrap();
+
+            assert_eq!(g.linear_extensions().collect::<Vec<_>>(), vec![vec![]]);
+            assert_eq!(g.count_linear_extensions(), Some(1));
+        }
+
+        #[test]
+        fn antichain_count() {
+            let g: PrecedenceGraph = create_graph!(Nodes: [1, 2, 3, 4, 5, 6], Edges: []).unwrap();
+
+            assert_eq!(g.count_linear_extensions(), Some(720));
+            assert_eq!(g.linear_extensions().count(), 720);
+        }
+
+        #[test]
+        fn sampling() {
             let g = create_graph!(
                 Nodes: [],
-                Edges: [1 => 3, 2 => 3, 3 => 5, 4 => 5]
+                Edges: [1 => [2, 3] => 4, 3 => 5]
             )
             .unwrap();
 
-            let res = g.msf_list();
+            let mut rng = StdRng::seed_from_u64(7);
+            let mut seen = HashSet::new();
 
-            assert!(matches!(res[0], 1 | 2));
-            assert!(matches!(res[1], 1 | 2));
-            assert!(matches!(res[2], 3 | 4));
-            assert!(matches!(res[3], 3 | 4));
-            assert!(matches!(res[4], 5));
+            for _ in 0..200 {
+                let extension = g.sample_linear_extension(&mut rng).unwrap();
 
-            let g = create_graph!(
-                Nodes: [],
-                Edges: [1 => 4, 2 => 4, 3 => 4, 4 => 5, 4 => 6]
+                assert!(respects_edges(&g, &extension));
+                seen.insert(extension);
+            }
+
+            assert_eq!(seen.len(), 5);
+        }
+
+        #[test]
+        fn sampling_layers() {
+            // Layers of four nodes, every node of a layer precedes every node of the next one.
+            let layers = |count: u32| {
+                PrecedenceGraph::new(
+                    (0..4 * count).collect(),
+                    (0..4 * (count - 1))
+                        .flat_map(|node| (0..4).map(move |next| (node, (node / 4 + 1) * 4 + next)))
+                        .collect(),
+                )
+                .unwrap()
+            };
+
+            let mut rng = StdRng::seed_from_u64(7);
+
+            let g = layers(20);
+            let first_layers: HashSet<Vec<u32>> = (0..20)
+                .map(|_| g.sample_linear_extension(&mut rng).unwrap()[..4].to_vec())
+                .collect();
+
+            assert!(first_layers.len() > 1);
+            assert!(first_layers
+                .iter()
+                .all(|layer| layer.iter().all(|&node| node < 4)));
+
+            let g = layers(250);
+
+            assert_eq!(g.count_linear_extensions(), None);
+            assert_eq!(g.sample_linear_extension(&mut rng), None);
+        }
+
+        #[test]
+        fn sampling_approx() {
+            // Sixteen parallel chains of two nodes, too wide for the exact sampler.
+            let g = PrecedenceGraph::new(
+                (0..32).collect(),
+                (0..16).map(|node| (2 * node, 2 * node + 1)).collect(),
             )
             .unwrap();
 
-            let res = g.msf_list();
+            let mut rng = StdRng::seed_from_u64(7);
+            let mut seen = HashSet::new();
 
-            assert!(matches!(res[0], 1 | 2 | 3));
-            assert!(matches!(res[1], 1 | 2 | 3));
-            assert!(matches!(res[2], 1 | 2 | 3));
-            assert!(matches!(res[3], 4));
-            assert!(matches!(res[4], 5 | 6));
-            assert!(matches!(res[5], 5 | 6));
+            for _ in 0..50 {
+                let extension = g.sample_linear_extension_approx(&mut rng, 20_000);
 
+                assert_eq!(extension.len(), 32);
+                assert!(respects_edges(&g, &extension));
+                seen.insert(extension);
+            }
+
+            assert_eq!(seen.len(), 50);
+
+            let g = create_graph!(Nodes: [], Edges: [1 => [2, 3] => 4, 3 => 5]).unwrap();
+            let seen: HashSet<Vec<i32>> = (0..200)
+                .map(|_| g.sample_linear_extension_approx(&mut rng, 100))
+                .collect();
+
+            assert_eq!(seen.len(), 5);
+            assert!(seen.iter().all(|extension| respects_edges(&g, extension)));
+        }
+    }
+
+    mod dimension {
+        use crate::create_graph;
+
+        #[test]
+        fn two_dimensional() {
             let g = create_graph!(
                 Nodes: [],
-                Edges: [1 => 4, 2 => 4, 2 => 3, 4 => 5, 3 => 5]
+                Edges: [1 => [2, 3] => 4, 3 => 5]
             )
             .unwrap();
 
-            let res = g.msf_list();
+            assert!(g.is_two_dimensional());
 
-            assert!(matches!(res[0], 2));
-            assert!(matches!(res[1], 1));
-            assert!(matches!(res[2], 3 | 4));
-            assert!(matches!(res[3], 3 | 4));
-            assert!(matches!(res[4], 5));
+            let (first, second) = g.two_dimensional_realizer().unwrap();
+            let position = |order: &[i32], node| order.iter().position(|&other| other == node);
+
+            for a in 1..=5 {
+                for b in 1..=5 {
+                    let below = |order: &[i32]| position(order, a) < position(order, b);
+
+                    assert_eq!(below(&first) && below(&second), g.reaches(&a, &b));
+                }
+            }
+
+            assert_eq!(g.dimension_upper_bound(), 2);
         }
 
         #[test]
-        fn overinterval() {
+        fn standard_example() {
+            // Every `a` is below every `b` except its own, the dimension is three.
             let g = create_graph!(
-            Nodes: [],
-            Edges: [10 => 5, 10 => 8, 9 => 8, 8 => 6, 6 => 4, 4 => 2, 7 => 5, 7 => 4, 5 => 3, 3 => 1]
-        )
-        .unwrap();
+                Nodes: [],
+                Edges: [1 => [5, 6], 2 => [4, 6], 3 => [4, 5]]
+            )
+            .unwrap();
 
-            let res = g.msf_list();
+            assert!(!g.is_two_dimensional());
+            assert_eq!(g.dimension_upper_bound(), 3);
 
-            assert!(matches!(res[0], 10));
-            assert!(matches!(res[1], 7));
-            assert!(matches!(res[2], 9));
-            assert!(matches!(res[3], 8));
-            assert!(matches!(res[4], 5 | 6));
-            assert!(matches!(res[5], 5 | 6));
-            assert!(matches!(res[6], 3 | 4));
-            assert!(matches!(res[7], 3 | 4));
-            assert!(matches!(res[8], 1 | 2));
-            assert!(matches!(res[9], 1 | 2));
+            let g = create_graph!(Nodes: [], Edges: [1 => 2 => 3]).unwrap();
+
+            assert!(g.is_two_dimensional());
+            assert_eq!(g.dimension_upper_bound(), 1);
         }
     }
 
-    mod gs {
+    mod forest {
         use super::*;
         use crate::create_graph;
 
         #[test]
-        fn quasi_interval_order() {
+        fn in_and_out_forests() {
             let g = create_graph!(
-                Nodes: [],
-                Edges: [1 => 2, 4 => 5]
+                Nodes: [7],
+                Edges: [[1, 2] => 3 => 6, [4, 5] => 6, 1 => 6]
             )
             .unwrap();
 
-            let res = g.gc_list();
-
-            assert!(matches!(res[0], 1 | 4));
-            assert!(matches!(res[1], 1 | 4));
-            assert!(matches!(res[2], 2 | 5));
-            assert!(matches!(res[2], 2 | 5));
+            assert!(g.is_in_forest());
+            assert!(!g.is_out_forest());
+            assert!(g.is_opposing_forest());
+            assert!(!g.is_chain_union());
+            assert_eq!(
+                g.in_forest_roots()
+                    .unwrap()
+                    .into_iter()
+                    .collect::<HashSet<_>>(),
+                HashSet::from([6, 7])
+            );
+            assert_eq!(g.out_forest_roots(), None);
 
             let g = create_graph!(
                 Nodes: [],
-                Edges: [1 => 4, 1 => 6, 2 => 4, 2 => 5, 2 => 6, 3 => 6, 5 => 7, 6 => 7]
+                Edges: [1 => [2, 3], 4 => 5 => 6, [7, 8] => 9]
             )
             .unwrap();
 
-            let res = g.gc_list();
-
-            assert!(matches!(res[0], 2));
-            assert!(matches!(res[1], 1));
-            assert!(matches!(res[2], 3));
-            assert!(matches!(res[3], 5 | 6));
-            assert!(matches!(res[4], 5 | 6));
-            assert!(matches!(res[5], 4 | 7));
-            assert!(matches!(res[6], 4 | 7));
+            assert!(!g.is_in_forest());
+            assert!(!g.is_out_forest());
+            assert!(g.is_opposing_forest());
 
             let g = create_graph!(
                 Nodes: [],
-                Edges: [1 => 3, 2 => 3, 3 => 5, 4 => 5]
+                Edges: [1 => [2, 3] => 4]
             )
             .unwrap();
 
-            let res = g.gc_list();
+            assert!(!g.is_opposing_forest());
+        }
 
-            assert!(matches!(res[0], 1 | 2));
-            assert!(matches!(res[1], 1 | 2));
+        #[test]
+        fn chains() {
+            let g = create_graph!(
+                Nodes: [5],
+                Edges: [1 => 2 => 3, 1 => 3, 4 => 6]
+            )
+            .unwrap();
+
+            assert!(g.is_chain_union());
+            assert!(g.is_in_forest());
+            assert!(g.is_out_forest());
+            assert!(g.is_opposing_forest());
+        }
+    }
+
+    mod comparability {
+        use super::*;
+        use crate::create_graph;
+        use std::collections::HashMap;
+
+        #[test]
+        fn graphs() {
+            let g = create_graph!(
+                Nodes: [4],
+                Edges: [1 => 2 => 3]
+            )
+            .unwrap();
+
+            assert_eq!(
+                g.comparability_graph(),
+                HashMap::from([
+                    (1, HashSet::from([2, 3])),
+                    (2, HashSet::from([1, 3])),
+                    (3, HashSet::from([1, 2])),
+                    (4, HashSet::new()),
+                ])
+            );
+            assert_eq!(
+                g.incomparability_graph(),
+                HashMap::from([
+                    (1, HashSet::from([4])),
+                    (2, HashSet::from([4])),
+                    (3, HashSet::from([4])),
+                    (4, HashSet::from([1, 2, 3])),
+                ])
+            );
+
+            assert!(g.is_comparability_edge(&3, &1));
+            assert!(!g.is_comparability_edge(&1, &1));
+            assert!(g.is_incomparability_edge(&4, &2));
+            assert!(!g.is_incomparability_edge(&4, &5));
+        }
+    }
+
+    mod algebra {
+        use super::*;
+        use crate::create_graph;
+        use std::collections::HashMap;
+
+        #[test]
+        fn dual_and_induced() {
+            let g = create_graph!(
+                Nodes: [5],
+                Edges: [1 => 2 => [3, 4]]
+            )
+            .unwrap();
+
+            assert_eq!(
+                g.dual(),
+                create_graph!(Nodes: [5], Edges: [[3, 4] => 2 => 1]).unwrap()
+            );
+            assert!(g.dual().reaches(&4, &1));
+
+            assert_eq!(
+                g.induced_subgraph(&HashSet::from([1, 3, 4, 6])),
+                create_graph!(Nodes: [], Edges: [1 => [3, 4]]).unwrap()
+            );
+        }
+
+        #[test]
+        fn compositions() {
+            let a = create_graph!(Nodes: [], Edges: [1 => [2, 3]]).unwrap();
+            let b = create_graph!(Nodes: [], Edges: [[4, 5] => 6]).unwrap();
+
+            assert_eq!(
+                a.parallel_compose(&b).unwrap(),
+                create_graph!(Nodes: [], Edges: [1 => [2, 3], [4, 5] => 6]).unwrap()
+            );
+            assert_eq!(
+                a.series_compose(&b).unwrap(),
+                create_graph!(Nodes: [], Edges: [1 => [2, 3] => [4, 5] => 6]).unwrap()
+            );
+
+            assert!(matches!(
+                a.series_compose(&a),
+                Err(PrecedenceGraphError::DuplicateNode { node: 1 | 2 | 3 })
+            ));
+        }
+
+        #[test]
+        fn lexicographic_sum() {
+            let outer = create_graph!(Nodes: [], Edges: ['a' => 'b' => 'c']).unwrap();
+            let parts = HashMap::from([
+                ('a', create_graph!(Nodes: [1, 2], Edges: []).unwrap()),
+                ('c', create_graph!(Nodes: [], Edges: [3 => 4]).unwrap()),
+            ]);
+
+            assert_eq!(
+                outer.lexicographic_sum(&parts).unwrap(),
+                create_graph!(Nodes: [], Edges: [[1, 2] => 3 => 4]).unwrap()
+            );
+
+            let parts = HashMap::from([
+                ('a', create_graph!(Nodes: [1], Edges: []).unwrap()),
+                ('b', create_graph!(Nodes: [1], Edges: []).unwrap()),
+            ]);
+
+            assert!(outer.lexicographic_sum(&parts).is_err());
+        }
+    }
+
+    mod isomorphism {
+        use super::*;
+        use crate::create_graph;
+
+        #[test]
+        fn relabelled() {
+            let g = create_graph!(
+                Nodes: [7],
+                Edges: [1 => [2, 3] => 4, 3 => 5 => 6, 1 => 4]
+            )
+            .unwrap();
+            let h = create_graph!(
+                Nodes: ["g"],
+                Edges: ["a" => ["c", "b"] => "d", "b" => "e" => "f"]
+            )
+            .unwrap();
+
+            assert_eq!(g.canonical_form(), h.canonical_form());
+
+            let mapping = g.is_isomorphic(&h).unwrap();
+
+            assert_eq!(mapping[&3], "b");
+            assert_eq!(mapping[&7], "g");
+            for a in 1..=7 {
+                for b in 1..=7 {
+                    assert_eq!(g.reaches(&a, &b), h.reaches(&mapping[&a], &mapping[&b]));
+                }
+            }
+        }
+
+        #[test]
+        fn not_isomorphic() {
+            let g = create_graph!(Nodes: [], Edges: [1 => [2, 3]]).unwrap();
+            let h = create_graph!(Nodes: [], Edges: [[1, 2] => 3]).unwrap();
+
+            assert_eq!(g.is_isomorphic(&h), None);
+            assert_ne!(g.canonical_form(), h.canonical_form());
+            assert_eq!(g.canonical_form(), g.dual().dual().canonical_form());
+
+            let symmetric: PrecedenceGraph = PrecedenceGraph::new(
+                (0..40).collect(),
+                (0..20).map(|node| (node, node + 20)).collect(),
+            )
+            .unwrap();
+
+            assert!(symmetric.is_isomorphic(&symmetric.dual()).is_some());
+        }
+    }
+
+    mod order_diff {
+        use crate::create_graph;
+
+        #[test]
+        fn same_order() {
+            let g = create_graph!(Nodes: [4], Edges: [1 => 2 => 3]).unwrap();
+            let h = create_graph!(Nodes: [4], Edges: [1 => 2 => 3, 1 => 3]).unwrap();
+
+            assert_ne!(g, h);
+            assert!(g.same_order_as(&h));
+            assert!(g.is_refinement_of(&h));
+            assert!(g.order_diff(&h).is_empty());
+
+            let isolated = create_graph!(Nodes: [5], Edges: [1 => 2 => 3]).unwrap();
+
+            assert!(!g.same_order_as(&isolated));
+        }
+
+        #[test]
+        fn diff() {
+            let g = create_graph!(Nodes: [], Edges: [1 => 2 => 3, 4 => 3]).unwrap();
+            let h = create_graph!(Nodes: [], Edges: [1 => 2, 4 => 3 => 2]).unwrap();
+
+            assert!(!g.is_refinement_of(&h));
+
+            let mut diff = g.order_diff(&h);
+            diff.added.sort();
+            diff.removed.sort();
+
+            assert_eq!(diff.added, vec![(3, 2), (4, 2)]);
+            assert_eq!(diff.removed, vec![(1, 3), (2, 3)]);
+
+            let finer = create_graph!(Nodes: [], Edges: [1 => 2 => 3, 4 => 1]).unwrap();
+
+            assert!(finer.is_refinement_of(&g));
+            assert!(!g.is_refinement_of(&finer));
+        }
+    }
+
+    mod linear_order {
+        use super::*;
+        use crate::create_graph;
+
+        #[test]
+        fn is_linear_order() {
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 2, 2 => 3]
+            )
+            .unwrap();
+
+            assert!(g.is_linear_order());
+
+            let g: PrecedenceGraph = create_graph!(
+                Nodes: [],
+                Edges: []
+            )
+            .unwrap();
+
+            assert!(g.is_linear_order());
+        }
+
+        #[test]
+        fn is_not_linear_order() {
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 2, 4 => 5]
+            )
+            .unwrap();
+
+            assert!(!g.is_linear_order());
+        }
+    }
+
+    mod msf {
+        use crate::create_graph;
+
+        #[test]
+        fn general() {
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [5 => 1, 5 => 2, 5 => 3, 7 => 6, 6 => 4]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert!(matches!(res[0], 5));
+            assert!(matches!(res[1], 7));
+            assert!(matches!(res[2], 6));
+            assert!(matches!(res[3], 1 | 2 | 3 | 4));
+            assert!(matches!(res[4], 1 | 2 | 3 | 4));
+            assert!(matches!(res[5], 1 | 2 | 3 | 4));
+            assert!(matches!(res[6], 1 | 2 | 3 | 4));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [8 => 5, 5 => 1, 5 => 2, 5 => 3, 7 => 6, 6 => 4]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert!(matches!(res[0], 8));
+            assert!(matches!(res[1], 5 | 7));
+            assert!(matches!(res[2], 5 | 7));
+            assert!(matches!(res[3], 6));
+            assert!(matches!(res[4], 1 | 2 | 3 | 4));
+            assert!(matches!(res[5], 1 | 2 | 3 | 4));
+            assert!(matches!(res[6], 1 | 2 | 3 | 4));
+            assert!(matches!(res[7], 1 | 2 | 3 | 4));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 3, 2 => 3, 3 => 5, 4 => 6, 6 => 5]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert!(matches!(res[0], 1 | 2 | 4));
+            assert!(matches!(res[1], 1 | 2 | 4));
+            assert!(matches!(res[2], 1 | 2 | 4));
+            assert!(matches!(res[3], 3 | 6));
+            assert!(matches!(res[4], 3 | 6));
+            assert!(matches!(res[5], 5));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 3, 1 => 5, 2 => 3, 2 => 6, 5 => 7, 6 => 7, 3 => 8, 7 => 9, 8 => 9]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert!(matches!(res[0], 1 | 2));
+            assert!(matches!(res[1], 1 | 2));
+            assert!(matches!(res[2], 3 | 5 | 6));
+            assert!(matches!(res[3], 3 | 5 | 6));
+            assert!(matches!(res[4], 3 | 5 | 6));
+            assert!(matches!(res[5], 7 | 8));
+            assert!(matches!(res[6], 7 | 8));
+            assert!(matches!(res[7], 9));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 2, 1 => 3, 2 => 4, 4 => 7, 7 => 8, 3 => 5, 3 => 6, 5 => 8, 6 => 8]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert!(matches!(res[0], 1));
+            assert!(matches!(res[1], 2 | 3));
+            assert!(matches!(res[2], 2 | 3));
+            assert!(matches!(res[3], 4));
+            assert!(matches!(res[4], 5 | 6 | 7));
+            assert!(matches!(res[5], 5 | 6 | 7));
+            assert!(matches!(res[6], 5 | 6 | 7));
+            assert!(matches!(res[7], 8));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 2, 1 => 3, 2 => 4, 4 => 7, 7 => 10, 3 => 5, 5 => 6, 6 => 8, 6 => 9, 8 => 10, 9 => 10]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert!(matches!(res[0], 1));
+            assert!(matches!(res[1], 3));
+            assert!(matches!(res[2], 5));
+            assert!(matches!(res[3], 2 | 6));
+            assert!(matches!(res[4], 2 | 6));
+            assert!(matches!(res[5], 4));
+            assert!(matches!(res[6], 7 | 8 | 9));
+            assert!(matches!(res[7], 7 | 8 | 9));
+            assert!(matches!(res[8], 7 | 8 | 9));
+            assert!(matches!(res[9], 10));
+        }
+
+        #[test]
+        fn quasi_interval_order() {
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 2, 4 => 5]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert!(matches!(res[0], 1 | 4));
+            assert!(matches!(res[1], 1 | 4));
+            assert!(matches!(res[2], 2 | 5));
+            assert!(matches!(res[2], 2 | 5));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 4, 1 => 6, 2 => 4, 2 => 5, 2 => 6, 3 => 6, 5 => 7, 6 => 7]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert!(matches!(res[0], 2));
+            assert!(matches!(res[1], 1));
+            assert!(matches!(res[2], 3));
+            assert!(matches!(res[3], 5 | 6));
+            assert!(matches!(res[4], 5 | 6));
+            assert!(matches!(res[5], 4 | 7));
+            assert!(matches!(res[6], 4 | 7));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 3, 2 => 3, 3 => 5, 4 => 5]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert!(matches!(res[0], 1 | 2));
+            assert!(matches!(res[1], 1 | 2));
+            assert!(matches!(res[2], 3 | 4));
+            assert!(matches!(res[3], 3 | 4));
+            assert!(matches!(res[4], 5));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 4, 2 => 4, 3 => 4, 4 => 5, 4 => 6]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert!(matches!(res[0], 1 | 2 | 3));
+            assert!(matches!(res[1], 1 | 2 | 3));
+            assert!(matches!(res[2], 1 | 2 | 3));
+            assert!(matches!(res[3], 4));
+            assert!(matches!(res[4], 5 | 6));
+            assert!(matches!(res[5], 5 | 6));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 4, 2 => 4, 2 => 3, 4 => 5, 3 => 5]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert!(matches!(res[0], 2));
+            assert!(matches!(res[1], 1));
+            assert!(matches!(res[2], 3 | 4));
+            assert!(matches!(res[3], 3 | 4));
+            assert!(matches!(res[4], 5));
+        }
+
+        #[test]
+        fn overinterval() {
+            let g = create_graph!(
+            Nodes: [],
+            Edges: [10 => 5, 10 => 8, 9 => 8, 8 => 6, 6 => 4, 4 => 2, 7 => 5, 7 => 4, 5 => 3, 3 => 1]
+        )
+        .unwrap();
+
+            let res = g.msf_list();
+
+            assert!(matches!(res[0], 10));
+            assert!(matches!(res[1], 7));
+            assert!(matches!(res[2], 9));
+            assert!(matches!(res[3], 8));
+            assert!(matches!(res[4], 5 | 6));
+            assert!(matches!(res[5], 5 | 6));
+            assert!(matches!(res[6], 3 | 4));
+            assert!(matches!(res[7], 3 | 4));
+            assert!(matches!(res[8], 1 | 2));
+            assert!(matches!(res[9], 1 | 2));
+        }
+    }
+
+    mod gs {
+        use crate::create_graph;
+
+        #[test]
+        fn quasi_interval_order() {
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 2, 4 => 5]
+            )
+            .unwrap();
+
+            let res = g.gc_list();
+
+            assert!(matches!(res[0], 1 | 4));
+            assert!(matches!(res[1], 1 | 4));
+            assert!(matches!(res[2], 2 | 5));
+            assert!(matches!(res[2], 2 | 5));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 4, 1 => 6, 2 => 4, 2 => 5, 2 => 6, 3 => 6, 5 => 7, 6 => 7]
+            )
+            .unwrap();
+
+            let res = g.gc_list();
+
+            assert!(matches!(res[0], 2));
+            assert!(matches!(res[1], 1));
+            assert!(matches!(res[2], 3));
+            assert!(matches!(res[3], 5 | 6));
+            assert!(matches!(res[4], 5 | 6));
+            assert!(matches!(res[5], 4 | 7));
+            assert!(matches!(res[6], 4 | 7));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 3, 2 => 3, 3 => 5, 4 => 5]
+            )
+            .unwrap();
+
+            let res = g.gc_list();
+
+            assert!(matches!(res[0], 1 | 2));
+            assert!(matches!(res[1], 1 | 2));
+            assert!(matches!(res[2], 3 | 4));
+            assert!(matches!(res[3], 3 | 4));
+            assert!(matches!(res[4], 5));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 4, 2 => 4, 3 => 4, 4 => 5, 4 => 6]
+            )
+            .unwrap();
+
+            let res = g.gc_list();
+
+            assert!(matches!(res[0], 1 | 2 | 3));
+            assert!(matches!(res[1], 1 | 2 | 3));
+            assert!(matches!(res[2], 1 | 2 | 3));
+            assert!(matches!(res[3], 4));
+            assert!(matches!(res[4], 5 | 6));
+            assert!(matches!(res[5], 5 | 6));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 4, 2 => 4, 2 => 3, 4 => 5, 3 => 5]
+            )
+            .unwrap();
+
+            let res = g.gc_list();
+
+            assert!(matches!(res[0], 2));
+            assert!(matches!(res[1], 1));
             assert!(matches!(res[2], 3 | 4));
             assert!(matches!(res[3], 3 | 4));
             assert!(matches!(res[4], 5));
+        }
+
+        #[test]
+        fn gc_general() {
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [5 => 1, 5 => 2, 5 => 3, 7 => 6, 6 => 4]
+            )
+            .unwrap();
+
+            let res = g.gc_list();
+
+            assert!(matches!(res[0], 7));
+            assert!(matches!(res[1], 5 | 6));
+            assert!(matches!(res[2], 5 | 6));
+            assert!(matches!(res[3], 1 | 2 | 3 | 4));
+            assert!(matches!(res[4], 1 | 2 | 3 | 4));
+            assert!(matches!(res[5], 1 | 2 | 3 | 4));
+            assert!(matches!(res[6], 1 | 2 | 3 | 4));
+        }
+
+        #[test]
+        fn gc_overinterval() {
+            let g = create_graph!(
+            Nodes: [],
+            Edges: [10 => 5, 10 => 8, 9 => 8, 8 => 6, 6 => 4, 4 => 2, 7 => 5, 7 => 4, 5 => 3, 3 => 1]
+        )
+        .unwrap();
+
+            let res = g.gc_list();
+
+            assert!(matches!(res[0], 10));
+            assert!(matches!(res[1], 9));
+            assert!(matches!(res[2], 7 | 8));
+            assert!(matches!(res[3], 7 | 8));
+            assert!(matches!(res[4], 5 | 6));
+            assert!(matches!(res[5], 5 | 6));
+            assert!(matches!(res[6], 3 | 4));
+            assert!(matches!(res[7], 3 | 4));
+            assert!(matches!(res[8], 1 | 2));
+            assert!(matches!(res[9], 1 | 2));
+        }
+    }
+
+    mod schedule {
+        use crate::create_graph;
+
+        #[test]
+        fn quasi_interval_order() {
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 2, 4 => 5]
+            )
+            .unwrap();
+
+            let res = g.gc_schedule(&[2, 2]);
+
+            assert!(matches!(
+                res[0][..],
+                [Some(1), Some(4)] | [Some(4), Some(1)]
+            ));
+            assert!(matches!(
+                res[1][..],
+                [Some(2), Some(5)] | [Some(5), Some(2)]
+            ));
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 4, 1 => 6, 2 => 4, 2 => 5, 2 => 6, 3 => 6, 5 => 7, 6 => 7]
+            )
+            .unwrap();
+
+            let res = g.msf_schedule(&[3, 3, 2]);
+
+            assert!(matches!(res[0][..], [Some(2), Some(1), Some(3)]));
+            assert!(matches!(
+                res[1][..],
+                [Some(5), Some(6), Some(4)]
+                    | [Some(5), Some(6), None]
+                    | [Some(6), Some(5), Some(4)]
+                    | [Some(6), Some(5), None]
+            ));
+            assert!(matches!(
+                res[2][..],
+                [Some(4), Some(7)] | [Some(7), Some(4)] | [Some(7), None]
+            ));
 
             let g = create_graph!(
                 Nodes: [],
-                Edges: [1 => 4, 2 => 4, 3 => 4, 4 => 5, 4 => 6]
+                Edges: [1 => 4, 2 => 4, 2 => 3, 4 => 5, 3 => 5]
             )
             .unwrap();
 
-            let res = g.gc_list();
+            let res = g.msf_schedule(&[3, 2, 2]);
 
-            assert!(matches!(res[0], 1 | 2 | 3));
-            assert!(matches!(res[1], 1 | 2 | 3));
-            assert!(matches!(res[2], 1 | 2 | 3));
-            assert!(matches!(res[3], 4));
-            assert!(matches!(res[4], 5 | 6));
-            assert!(matches!(res[5], 5 | 6));
+            assert!(matches!(res[0][..], [Some(2), Some(1), None]));
+            assert!(matches!(
+                res[1][..],
+                [Some(3), Some(4)] | [Some(4), Some(3)]
+            ));
+            assert!(matches!(res[2][..], [Some(5), None]));
+        }
 
+        #[test]
+        fn general() {
             let g = create_graph!(
                 Nodes: [],
-                Edges: [1 => 4, 2 => 4, 2 => 3, 4 => 5, 3 => 5]
+                Edges: [5 => 1, 5 => 2, 5 => 3, 7 => 6, 6 => 4]
             )
             .unwrap();
 
-            let res = g.gc_list();
+            let res = g.gc_schedule(&[2, 2, 2, 3]);
 
-            assert!(matches!(res[0], 2));
-            assert!(matches!(res[1], 1));
-            assert!(matches!(res[2], 3 | 4));
-            assert!(matches!(res[3], 3 | 4));
-            assert!(matches!(res[4], 5));
+            assert!(matches!(res[0][..], [Some(7), Some(5)] | [Some(7), None]));
+            assert!(matches!(
+                res[1][..],
+                [Some(5), Some(1)]
+                    | [Some(5), Some(2)]
+                    | [Some(5), Some(3)]
+                    | [Some(5), Some(4)]
+                    | [Some(6), Some(1)]
+                    | [Some(6), Some(2)]
+                    | [Some(6), Some(3)]
+                    | [Some(6), Some(4)]
+                    | [Some(6), Some(5)]
+            ));
+            assert!(matches!(
+                res[2][..],
+                [Some(1), Some(2)]
+                    | [Some(1), Some(3)]
+                    | [Some(1), Some(4)]
+                    | [Some(2), Some(1)]
+                    | [Some(2), Some(3)]
+                    | [Some(2), Some(4)]
+                    | [Some(3), Some(1)]
+                    | [Some(3), Some(2)]
+                    | [Some(3), Some(4)]
+                    | [Some(4), Some(1)]
+                    | [Some(4), Some(2)]
+                    | [Some(4), Some(3)]
+            ));
+            assert!(matches!(
+                res[3][..],
+                [Some(1), None, None]
+                    | [Some(2), None, None]
+                    | [Some(3), None, None]
+                    | [Some(4), None, None]
+                    | [Some(1), Some(2), None]
+                    | [Some(1), Some(3), None]
+                    | [Some(1), Some(4), None]
+                    | [Some(2), Some(1), None]
+                    | [Some(2), Some(3), None]
+                    | [Some(2), Some(4), None]
+                    | [Some(3), Some(1), None]
+                    | [Some(3), Some(2), None]
+                    | [Some(3), Some(4), None]
+                    | [Some(4), Some(1), None]
+                    | [Some(4), Some(2), None]
+                    | [Some(4), Some(3), None]
+            ));
         }
 
         #[test]
-        fn gc_general() {
+        fn small_time_amount() {
             let g = create_graph!(
                 Nodes: [],
-                Edges: [5 => 1, 5 => 2, 5 => 3, 7 => 6, 6 => 4]
+                Edges: [1 => 2, 4 => 5]
             )
             .unwrap();
 
-            let res = g.gc_list();
+            let res = g.gc_schedule(&[2]);
 
-            assert!(matches!(res[0], 7));
-            assert!(matches!(res[1], 5 | 6));
-            assert!(matches!(res[2], 5 | 6));
-            assert!(matches!(res[3], 1 | 2 | 3 | 4));
-            assert!(matches!(res[4], 1 | 2 | 3 | 4));
-            assert!(matches!(res[5], 1 | 2 | 3 | 4));
-            assert!(matches!(res[6], 1 | 2 | 3 | 4));
+            assert!(matches!(
+                res[0][..],
+                [Some(1), Some(4)] | [Some(4), Some(1)]
+            ));
         }
 
         #[test]
-        fn gc_overinterval() {
+        fn big_time_amount() {
             let g = create_graph!(
-            Nodes: [],
-            Edges: [10 => 5, 10 => 8, 9 => 8, 8 => 6, 6 => 4, 4 => 2, 7 => 5, 7 => 4, 5 => 3, 3 => 1]
-        )
-        .unwrap();
+                Nodes: [],
+                Edges: [1 => 2, 4 => 5]
+            )
+            .unwrap();
 
-            let res = g.gc_list();
+            let res = g.gc_schedule(&[2, 2, 2, 2]);
 
-            assert!(matches!(res[0], 10));
-            assert!(matches!(res[1], 9));
-            assert!(matches!(res[2], 7 | 8));
-            assert!(matches!(res[3], 7 | 8));
-            assert!(matches!(res[4], 5 | 6));
-            assert!(matches!(res[5], 5 | 6));
-            assert!(matches!(res[6], 3 | 4));
-            assert!(matches!(res[7], 3 | 4));
-            assert!(matches!(res[8], 1 | 2));
-            assert!(matches!(res[9], 1 | 2));
+            assert!(matches!(
+                res[0][..],
+                [Some(1), Some(4)] | [Some(4), Some(1)]
+            ));
+            assert!(matches!(
+                res[1][..],
+                [Some(2), Some(5)] | [Some(5), Some(2)]
+            ));
+            assert!(matches!(res[2][..], [None, None]));
+            assert!(matches!(res[3][..], [None, None]));
         }
     }
 
-    mod schedule {
+    mod isolated_nodes {
         use super::*;
         use crate::create_graph;
 
         #[test]
-        fn quasi_interval_order() {
+        fn lists() {
             let g = create_graph!(
-                Nodes: [],
-                Edges: [1 => 2, 4 => 5]
+                Nodes: [3, 4],
+                Edges: [1 => 2]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert_eq!(res.len(), 4);
+            assert_eq!(res[0], 1);
+            assert!(res.contains(&3));
+            assert!(res.contains(&4));
+
+            let res = g.gc_list();
+
+            assert_eq!(res.len(), 4);
+            assert!(res.contains(&3));
+            assert!(res.contains(&4));
+            assert!(res.iter().position(|&v| v == 1) < res.iter().position(|&v| v == 2));
+        }
+
+        #[test]
+        fn schedules() {
+            let g = create_graph!(
+                Nodes: [3],
+                Edges: [1 => 2]
             )
             .unwrap();
 
@@ -564,145 +1502,566 @@ mod tests {
 
             assert!(matches!(
                 res[0][..],
-                [Some(1), Some(4)] | [Some(4), Some(1)]
+                [Some(1), Some(3)] | [Some(3), Some(1)] | [Some(1), None]
             ));
             assert!(matches!(
                 res[1][..],
-                [Some(2), Some(5)] | [Some(5), Some(2)]
+                [Some(2), None] | [Some(2), Some(3)] | [Some(3), Some(2)]
             ));
+            assert_eq!(res.iter().flatten().flatten().count(), 3);
+
+            let g: PrecedenceGraph = create_graph!(
+                Nodes: [1, 2, 3],
+                Edges: []
+            )
+            .unwrap();
+
+            let res = g.msf_schedule(&[3]);
+
+            assert_eq!(res[0].iter().flatten().count(), 3);
+        }
+
+        #[test]
+        fn orders() {
+            let g: PrecedenceGraph = create_graph!(
+                Nodes: [1],
+                Edges: []
+            )
+            .unwrap();
+
+            assert!(g.is_linear_order());
+            assert!(g.is_quasi_interval_order());
+
+            let g = create_graph!(
+                Nodes: [4],
+                Edges: [1 => 2, 2 => 3]
+            )
+            .unwrap();
+
+            assert!(!g.is_linear_order());
+            assert!(g.is_quasi_interval_order());
+
+            let g = create_graph!(
+                Nodes: [6],
+                Edges: [1 => 2, 1 => 3, 4 => 5]
+            )
+            .unwrap();
+
+            assert!(g.is_first());
+            assert!(!g.is_quasi_interval_order());
+        }
+    }
+
+    mod reachability {
+        use crate::{builder::PrecedenceGraphBuilder, create_graph};
+
+        #[test]
+        fn queries() {
+            let g = create_graph!(
+                Nodes: [6],
+                Edges: [1 => [2, 3], 2 => 4, 3 => 4 => 5]
+            )
+            .unwrap();
+
+            assert!(g.reaches(&1, &5));
+            assert!(!g.reaches(&5, &1));
+            assert!(!g.reaches(&1, &1));
+            assert!(!g.reaches(&1, &7));
+
+            assert!(g.is_comparable(&1, &1));
+            assert!(g.is_comparable(&5, &2));
+            assert!(!g.is_comparable(&2, &3));
+            assert!(!g.is_comparable(&6, &1));
+
+            let mut descendants: Vec<_> = g.descendants(&2).copied().collect();
+            descendants.sort();
+            assert_eq!(descendants, vec![4, 5]);
+
+            let mut ancestors: Vec<_> = g.ancestors(&4).copied().collect();
+            ancestors.sort();
+            assert_eq!(ancestors, vec![1, 2, 3]);
+
+            assert_eq!(g.descendants(&6).count(), 0);
+            assert_eq!(g.ancestors(&7).count(), 0);
+        }
+
+        #[test]
+        fn updated_after_editing() {
+            let mut g = create_graph!(
+                Nodes: [],
+                Edges: [1 => 2, 3 => 4]
+            )
+            .unwrap();
+
+            assert!(!g.reaches(&1, &4));
+
+            g.add_edge(2, 3).unwrap();
+            assert!(g.reaches(&1, &4));
+
+            g.remove_edge(&2, &3);
+            assert!(!g.reaches(&1, &4));
+
+            g.add_edge(2, 3).unwrap();
+            g.remove_node(&3);
+            assert!(!g.reaches(&1, &4));
+            assert!(g.reaches(&1, &2));
+        }
+
+        #[test]
+        fn large_graph() {
+            let chain: Vec<u32> = (0..300).collect();
+
+            let g = PrecedenceGraphBuilder::new()
+                .auto_nodes()
+                .chain(&chain)
+                .fan_out(0, [1000, 1001])
+                .build()
+                .unwrap();
+
+            assert!(g.reaches(&0, &299));
+            assert!(g.reaches(&70, &200));
+            assert!(!g.reaches(&200, &70));
+            assert!(!g.is_comparable(&1000, &299));
+            assert_eq!(g.descendants(&0).count(), 301);
+            assert_eq!(g.ancestors(&299).count(), 299);
+            assert!(!g.is_linear_order());
+            assert!(g.is_quasi_interval_order());
+        }
+    }
+
+    mod queries {
+        use crate::create_graph;
+
+        fn sorted<'a>(nodes: impl Iterator<Item = &'a i32>) -> Vec<i32> {
+            let mut nodes: Vec<_> = nodes.copied().collect();
+            nodes.sort();
+            nodes
+        }
+
+        #[test]
+        fn nodes_and_edges() {
+            let g = create_graph!(
+                Nodes: [6],
+                Edges: [1 => [2, 3], [2, 3] => 4 => 5]
+            )
+            .unwrap();
+
+            assert_eq!(g.node_count(), 6);
+            assert_eq!(g.edge_count(), 5);
+            assert!(g.contains_node(&6));
+            assert!(!g.contains_node(&7));
+            assert_eq!(sorted(g.nodes()), vec![1, 2, 3, 4, 5, 6]);
+
+            let mut edges: Vec<_> = g.edges().map(|(a, b)| (*a, *b)).collect();
+            edges.sort();
+            assert_eq!(edges, vec![(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]);
+
+            assert_eq!(sorted(g.sources()), vec![1, 6]);
+            assert_eq!(sorted(g.sinks()), vec![5, 6]);
+        }
+
+        #[test]
+        fn neighbours() {
+            let g = create_graph!(
+                Nodes: [6],
+                Edges: [1 => [2, 3], [2, 3] => 4 => 5]
+            )
+            .unwrap();
+
+            assert_eq!(sorted(g.successors(&1)), vec![2, 3]);
+            assert_eq!(sorted(g.predecessors(&4)), vec![2, 3]);
+            assert_eq!(g.successors(&7).count(), 0);
+
+            assert_eq!(g.in_degree(&4), Some(2));
+            assert_eq!(g.out_degree(&4), Some(1));
+            assert_eq!(g.in_degree(&6), Some(0));
+            assert_eq!(g.out_degree(&7), None);
+        }
+    }
+
+    mod transitive {
+        use crate::create_graph;
+
+        #[test]
+        fn reduction() {
+            let g = create_graph!(
+                Nodes: [6],
+                Edges: [1 => 2 => 3 => 4, 1 => 3, 1 => 4, 2 => 4, 5 => 4]
+            )
+            .unwrap();
+
+            let mut redundant = g.redundant_edges();
+            redundant.sort();
+            assert_eq!(redundant, vec![(1, 3), (1, 4), (2, 4)]);
+
+            let reduced = g.transitive_reduction();
+
+            assert_eq!(
+                reduced,
+                create_graph!(
+                    Nodes: [6],
+                    Edges: [1 => 2 => 3 => 4, 5 => 4]
+                )
+                .unwrap()
+            );
+            assert!(reduced.redundant_edges().is_empty());
+            assert!(reduced.reaches(&1, &4));
+        }
+
+        #[test]
+        fn closure() {
+            let g = create_graph!(
+                Nodes: [5],
+                Edges: [1 => 2 => 3, 4 => 3]
+            )
+            .unwrap();
+
+            let closed = g.transitive_closure();
+
+            assert_eq!(
+                closed,
+                create_graph!(
+                    Nodes: [5],
+                    Edges: [1 => 2 => 3, 1 => 3, 4 => 3]
+                )
+                .unwrap()
+            );
+            assert_eq!(closed.transitive_reduction(), g);
+        }
+    }
 
-            let g = create_graph!(
+    mod editing {
+        use super::*;
+        use crate::create_graph;
+
+        #[test]
+        fn add_and_remove() {
+            let mut g = create_graph!(
                 Nodes: [],
-                Edges: [1 => 4, 1 => 6, 2 => 4, 2 => 5, 2 => 6, 3 => 6, 5 => 7, 6 => 7]
+                Edges: [1 => 2]
             )
             .unwrap();
 
-            let res = g.msf_schedule(&[3, 3, 2]);
+            assert!(g.add_node(3));
+            assert!(!g.add_node(3));
+            assert!(g.add_edge(2, 3).unwrap());
+            assert!(!g.add_edge(2, 3).unwrap());
+            assert!(g.contains_edge(&2, &3));
+            assert!(g.is_linear_order());
 
-            assert!(matches!(res[0][..], [Some(2), Some(1), Some(3)]));
-            assert!(matches!(
-                res[1][..],
-                [Some(5), Some(6), Some(4)]
-                    | [Some(5), Some(6), None]
-                    | [Some(6), Some(5), Some(4)]
-                    | [Some(6), Some(5), None]
-            ));
-            assert!(matches!(
-                res[2][..],
-                [Some(4), Some(7)] | [Some(7), Some(4)] | [Some(7), None]
-            ));
+            assert_eq!(
+                g,
+                create_graph!(
+                    Nodes: [],
+                    Edges: [1 => 2, 2 => 3]
+                )
+                .unwrap()
+            );
+
+            assert!(g.remove_edge(&1, &2));
+            assert!(!g.remove_edge(&1, &2));
+            assert!(!g.contains_edge(&1, &2));
+
+            assert!(g.remove_node(&1));
+            assert!(!g.remove_node(&1));
+            assert_eq!(g.gc_list(), vec![2, 3]);
+        }
 
-            let g = create_graph!(
+        #[test]
+        fn remove_node_drops_edges() {
+            let mut g = create_graph!(
                 Nodes: [],
-                Edges: [1 => 4, 2 => 4, 2 => 3, 4 => 5, 3 => 5]
+                Edges: [1 => 2, 2 => 3, 3 => 4, 1 => 4]
             )
             .unwrap();
 
-            let res = g.msf_schedule(&[3, 2, 2]);
+            assert!(g.remove_node(&2));
 
-            assert!(matches!(res[0][..], [Some(2), Some(1), None]));
-            assert!(matches!(
-                res[1][..],
-                [Some(3), Some(4)] | [Some(4), Some(3)]
-            ));
-            assert!(matches!(res[2][..], [Some(5), None]));
+            assert_eq!(
+                g,
+                create_graph!(
+                    Nodes: [],
+                    Edges: [3 => 4, 1 => 4]
+                )
+                .unwrap()
+            );
+
+            assert!(g.remove_node(&4));
+            assert!(g.add_edge(1, 3).unwrap());
+
+            assert_eq!(g.msf_list(), vec![1, 3]);
         }
 
         #[test]
-        fn general() {
-            let g = create_graph!(
+        fn invalid_edges() {
+            let mut g = create_graph!(
                 Nodes: [],
-                Edges: [5 => 1, 5 => 2, 5 => 3, 7 => 6, 6 => 4]
+                Edges: [1 => 2, 2 => 3]
             )
             .unwrap();
 
-            let res = g.gc_schedule(&[2, 2, 2, 3]);
+            assert!(matches!(
+                g.add_edge(1, 4),
+                Err(PrecedenceGraphError::EdgeContainsNonexistentNode { node: 4 })
+            ));
+            assert!(matches!(
+                g.add_edge(2, 2),
+                Err(PrecedenceGraphError::SelfLoop { node: 2 })
+            ));
+            assert!(matches!(
+                g.add_edge(3, 1),
+                Err(PrecedenceGraphError::CycleDetected { cycle }) if cycle == vec![1, 2, 3]
+            ));
+            assert!(!g.contains_edge(&3, &1));
+        }
+    }
 
-            assert!(matches!(res[0][..], [Some(7), Some(5)] | [Some(7), None]));
+    mod validation {
+        use super::*;
+        use crate::validation::ValidationWarning;
+
+        #[test]
+        fn collects_every_problem() {
+            let report = PrecedenceGraph::validate(
+                [1, 2, 3, 4, 5, 6],
+                [
+                    (1, 10),
+                    (11, 12),
+                    (2, 2),
+                    (1, 2),
+                    (1, 2),
+                    (3, 4),
+                    (4, 5),
+                    (5, 3),
+                ],
+            );
+
+            assert!(!report.is_valid());
+            assert_eq!(report.errors.len(), 5);
             assert!(matches!(
-                res[1][..],
-                [Some(5), Some(1)]
-                    | [Some(5), Some(2)]
-                    | [Some(5), Some(3)]
-                    | [Some(5), Some(4)]
-                    | [Some(6), Some(1)]
-                    | [Some(6), Some(2)]
-                    | [Some(6), Some(3)]
-                    | [Some(6), Some(4)]
-                    | [Some(6), Some(5)]
+                report.errors[0],
+                PrecedenceGraphError::EdgeContainsNonexistentNode { node: 10 }
             ));
             assert!(matches!(
-                res[2][..],
-                [Some(1), Some(2)]
-                    | [Some(1), Some(3)]
-                    | [Some(1), Some(4)]
-                    | [Some(2), Some(1)]
-                    | [Some(2), Some(3)]
-                    | [Some(2), Some(4)]
-                    | [Some(3), Some(1)]
-                    | [Some(3), Some(2)]
-                    | [Some(3), Some(4)]
-                    | [Some(4), Some(1)]
-                    | [Some(4), Some(2)]
-                    | [Some(4), Some(3)]
+                report.errors[1],
+                PrecedenceGraphError::EdgeContainsNonexistentNode { node: 11 }
             ));
             assert!(matches!(
-                res[3][..],
-                [Some(1), None, None]
-                    | [Some(2), None, None]
-                    | [Some(3), None, None]
-                    | [Some(4), None, None]
-                    | [Some(1), Some(2), None]
-                    | [Some(1), Some(3), None]
-                    | [Some(1), Some(4), None]
-                    | [Some(2), Some(1), None]
-                    | [Some(2), Some(3), None]
-                    | [Some(2), Some(4), None]
-                    | [Some(3), Some(1), None]
-                    | [Some(3), Some(2), None]
-                    | [Some(3), Some(4), None]
-                    | [Some(4), Some(1), None]
-                    | [Some(4), Some(2), None]
-                    | [Some(4), Some(3), None]
+                report.errors[2],
+                PrecedenceGraphError::EdgeContainsNonexistentNode { node: 12 }
+            ));
+            assert!(matches!(
+                report.errors[3],
+                PrecedenceGraphError::SelfLoop { node: 2 }
             ));
+            assert!(matches!(
+                &report.errors[4],
+                PrecedenceGraphError::CycleDetected { cycle } if cycle.len() == 3
+            ));
+            assert_eq!(
+                report.warnings,
+                vec![ValidationWarning::DuplicateEdge {
+                    outcome: 1,
+                    income: 2
+                }]
+            );
         }
 
         #[test]
-        fn small_time_amount() {
-            let g = create_graph!(
-                Nodes: [],
-                Edges: [1 => 2, 4 => 5]
-            )
-            .unwrap();
+        fn warnings_only() {
+            let (g, report) =
+                PrecedenceGraph::new_with_report([1, 2, 3], [(1, 2), (2, 3), (1, 3)]).unwrap();
+
+            assert!(report.is_valid());
+            assert_eq!(
+                report.warnings,
+                vec![ValidationWarning::RedundantEdge {
+                    outcome: 1,
+                    income: 3
+                }]
+            );
+            assert!(g.is_linear_order());
 
-            let res = g.gc_schedule(&[2]);
+            let report = PrecedenceGraph::new_with_report([1, 2], [(1, 2), (2, 1)]).unwrap_err();
 
-            assert!(matches!(
-                res[0][..],
-                [Some(1), Some(4)] | [Some(4), Some(1)]
-            ));
+            assert_eq!(report.errors.len(), 1);
+        }
+    }
+
+    mod payloads {
+        use super::*;
+        use std::collections::HashMap;
+
+        #[derive(Clone, PartialEq, Debug)]
+        struct Task {
+            name: &'static str,
+            duration: u32,
+        }
+
+        fn tasks() -> PrecedenceGraph<u128, Task> {
+            let mut nodes = HashMap::new();
+            nodes.insert(
+                1,
+                Task {
+                    name: "fetch",
+                    duration: 2,
+                },
+            );
+            nodes.insert(
+                2,
+                Task {
+                    name: "build",
+                    duration: 5,
+                },
+            );
+
+            let mut edges = HashSet::new();
+            edges.insert((1, 2));
+
+            PrecedenceGraph::with_payloads(nodes, edges).unwrap()
         }
 
         #[test]
-        fn big_time_amount() {
-            let g = create_graph!(
-                Nodes: [],
-                Edges: [1 => 2, 4 => 5]
-            )
-            .unwrap();
+        fn construction_and_editing() {
+            let mut g = tasks();
 
-            let res = g.gc_schedule(&[2, 2, 2, 2]);
+            assert_eq!(g.payload(&1).unwrap().name, "fetch");
+            assert_eq!(g.payload(&3), None);
 
-            assert!(matches!(
-                res[0][..],
-                [Some(1), Some(4)] | [Some(4), Some(1)]
+            g.payload_mut(&2).unwrap().duration = 7;
+            assert_eq!(g.payload(&2).unwrap().duration, 7);
+
+            assert!(g.add_node_with_payload(
+                3,
+                Task {
+                    name: "test",
+                    duration: 1,
+                },
+            ));
+            assert!(!g.add_node_with_payload(
+                3,
+                Task {
+                    name: "other",
+                    duration: 1,
+                },
             ));
+            g.add_edge(2, 3).unwrap();
+            g.remove_node(&1);
+
+            assert_eq!(g.payload(&3).unwrap().name, "test");
+            assert_eq!(g.payload(&2).unwrap().name, "build");
+        }
+
+        #[test]
+        fn lists_and_schedules() {
+            let g = tasks();
+
+            let res = g.gc_list_with_payloads();
+
+            assert_eq!(res[0].0, 1);
+            assert_eq!(res[0].1.name, "fetch");
+            assert_eq!(res[1].1.name, "build");
+
+            let res = g.msf_schedule_with_payloads(&[1, 1, 1]);
+
+            assert_eq!(res[0][0].as_ref().unwrap().1.name, "fetch");
+            assert_eq!(res[1][0].as_ref().unwrap().1.duration, 5);
+            assert!(res[2][0].is_none());
+        }
+
+        #[test]
+        fn payloads_in_equality() {
+            let mut g = tasks();
+
+            assert_eq!(g, tasks());
+
+            g.payload_mut(&1).unwrap().duration = 3;
+
+            assert_ne!(g, tasks());
+        }
+    }
+
+    mod builder {
+        use crate::{
+            builder::PrecedenceGraphBuilder, create_graph, precedence_graph::PrecedenceGraphError,
+            validation::ValidationWarning,
+        };
+
+        #[test]
+        fn build() {
+            let g = PrecedenceGraphBuilder::new()
+                .auto_nodes()
+                .node(8)
+                .chain(&[1, 2, 3])
+                .fan_out(3, [4, 5])
+                .fan_in([4, 5], 6)
+                .edge(6, 7)
+                .extend_edges((10..12).map(|node| (7, node)))
+                .build()
+                .unwrap();
+
+            assert_eq!(
+                g,
+                create_graph!(
+                    Nodes: [8],
+                    Edges: [1 => 2, 2 => 3, 3 => 4, 3 => 5, 4 => 6, 5 => 6, 6 => 7, 7 => 10, 7 => 11]
+                )
+                .unwrap()
+            );
+        }
+
+        #[test]
+        fn payloads() {
+            let g = PrecedenceGraphBuilder::<&str, u32>::default()
+                .auto_nodes()
+                .node_with_payload("build", 5)
+                .node_with_payload("build", 1)
+                .edge("fetch", "build")
+                .build()
+                .unwrap();
+
+            assert_eq!(g.payload(&"build"), Some(&5));
+            assert_eq!(g.payload(&"fetch"), Some(&0));
+        }
+
+        #[test]
+        fn validation() {
+            let report = PrecedenceGraphBuilder::new()
+                .auto_nodes()
+                .chain(&[1, 2, 3, 1])
+                .edge(4, 4)
+                .build()
+                .unwrap_err();
+
+            assert_eq!(report.errors.len(), 2);
+
+            let report = PrecedenceGraphBuilder::new()
+                .node(1)
+                .node(2)
+                .chain(&[1, 2, 3])
+                .build()
+                .unwrap_err();
+
             assert!(matches!(
-                res[1][..],
-                [Some(2), Some(5)] | [Some(5), Some(2)]
+                report.errors[..],
+                [PrecedenceGraphError::EdgeContainsNonexistentNode { node: 3 }]
             ));
-            assert!(matches!(res[2][..], [None, None]));
-            assert!(matches!(res[3][..], [None, None]));
+
+            let (_, report) = PrecedenceGraphBuilder::new()
+                .node(1)
+                .node(2)
+                .chain(&[1, 2])
+                .edge(1, 2)
+                .build_with_report()
+                .unwrap();
+
+            assert_eq!(
+                report.warnings,
+                vec![ValidationWarning::DuplicateEdge {
+                    outcome: 1,
+                    income: 2
+                }]
+            );
         }
     }
 
@@ -748,6 +2107,129 @@ mod tests {
             assert_eq!(g1, g2);
         }
 
+        #[test]
+        fn create_graph_macro_chains() {
+            let g = create_graph!(
+                Nodes: [9],
+                Edges: [1 => 2 => [3, 4] => 5, [6, 7] => 8, 8 => 5]
+            )
+            .unwrap();
+
+            let g2 = create_graph!(
+                Nodes: [9],
+                Edges: [1 => 2, 2 => 3, 2 => 4, 3 => 5, 4 => 5, 6 => 8, 7 => 8, 8 => 5]
+            )
+            .unwrap();
+
+            assert_eq!(g, g2);
+
+            let first = 10;
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [first => (first + 1) => [first + 2, first + 3],]
+            )
+            .unwrap();
+
+            let g2 = create_graph!(
+                Nodes: [],
+                Edges: [10 => 11, 11 => 12, 11 => 13]
+            )
+            .unwrap();
+
+            assert_eq!(g, g2);
+        }
+
+        #[test]
+        fn create_graph_macro_long() {
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [
+                    0 => 1, 1 => 2, 2 => 3, 3 => 4, 4 => 5, 5 => 6, 6 => 7, 7 => 8, 8 => 9, 9 => 10,
+                    10 => 11, 11 => 12, 12 => 13, 13 => 14, 14 => 15, 15 => 16, 16 => 17, 17 => 18,
+                    18 => 19, 19 => 20, 20 => 21, 21 => 22, 22 => 23, 23 => 24, 24 => 25, 25 => 26,
+                    26 => 27, 27 => 28, 28 => 29, 29 => 30, 30 => 31, 31 => 32, 32 => 33, 33 => 34,
+                    34 => 35, 35 => 36, 36 => 37, 37 => 38, 38 => 39, 39 => 40, 40 => 41, 41 => 42,
+                    42 => 43, 43 => 44, 44 => 45, 45 => 46, 46 => 47, 47 => 48, 48 => 49, 49 => 50,
+                    50 => 51, 51 => 52, 52 => 53, 53 => 54, 54 => 55, 55 => 56, 56 => 57, 57 => 58,
+                    58 => 59, 59 => 60, 60 => 61, 61 => 62, 62 => 63, 63 => 64, 64 => 65, 65 => 66,
+                    66 => 67, 67 => 68, 68 => 69, 69 => 70, 70 => 71, 71 => 72, 72 => 73, 73 => 74,
+                    74 => 75, 75 => 76, 76 => 77, 77 => 78, 78 => 79, 79 => 80, 80 => 81, 81 => 82,
+                    82 => 83, 83 => 84, 84 => 85, 85 => 86, 86 => 87, 87 => 88, 88 => 89, 89 => 90,
+                    90 => 91, 91 => 92, 92 => 93, 93 => 94, 94 => 95, 95 => 96, 96 => 97, 97 => 98,
+                    98 => 99, 99 => 100, 100 => 101, 101 => 102, 102 => 103, 103 => 104, 104 => 105,
+                    105 => 106, 106 => 107, 107 => 108, 108 => 109, 109 => 110, 110 => 111,
+                    111 => 112, 112 => 113, 113 => 114, 114 => 115, 115 => 116, 116 => 117,
+                    117 => 118, 118 => 119, 119 => 120, 120 => 121, 121 => 122, 122 => 123,
+                    123 => 124, 124 => 125, 125 => 126, 126 => 127, 127 => 128, 128 => 129,
+                    129 => 130, 130 => 131, 131 => 132, 132 => 133, 133 => 134, 134 => 135,
+                    135 => 136, 136 => 137, 137 => 138, 138 => 139, 139 => 140, 140 => 141,
+                    141 => 142, 142 => 143, 143 => 144, 144 => 145, 145 => 146, 146 => 147,
+                    147 => 148, 148 => 149, 149 => 150, 150 => 151, 151 => 152, 152 => 153,
+                    153 => 154, 154 => 155, 155 => 156, 156 => 157, 157 => 158, 158 => 159,
+                    159 => 160, 160 => 161, 161 => 162, 162 => 163, 163 => 164, 164 => 165,
+                    165 => 166, 166 => 167, 167 => 168, 168 => 169, 169 => 170, 170 => 171,
+                    171 => 172, 172 => 173, 173 => 174, 174 => 175, 175 => 176, 176 => 177,
+                    177 => 178, 178 => 179, 179 => 180, 180 => 181, 181 => 182, 182 => 183,
+                    183 => 184, 184 => 185, 185 => 186, 186 => 187, 187 => 188, 188 => 189,
+                    189 => 190, 190 => 191, 191 => 192, 192 => 193, 193 => 194, 194 => 195,
+                    195 => 196, 196 => 197, 197 => 198, 198 => 199, 199 => 200, 200 => 201,
+                    201 => 202, 202 => 203, 203 => 204, 204 => 205, 205 => 206, 206 => 207,
+                    207 => 208, 208 => 209, 209 => 210, 210 => 211, 211 => 212, 212 => 213,
+                    213 => 214, 214 => 215, 215 => 216, 216 => 217, 217 => 218, 218 => 219,
+                    219 => 220
+                ]
+            )
+            .unwrap();
+
+            assert_eq!(g.edge_count(), 220);
+            assert!(g.is_linear_order());
+
+            let g = create_graph!(
+                Nodes: [],
+                Edges: [
+                    0 => 1 => 2 => 3 => 4 => 5 => 6 => 7 => 8 => 9 => 10 => 11 => 12 => 13 =>
+                    14 => 15 => 16 => 17 => 18 => 19 => 20 => 21 => 22 => 23 => 24 => 25 => 26 =>
+                    27 => 28 => 29 => 30 => 31 => 32 => 33 => 34 => 35 => 36 => 37 => 38 => 39 =>
+                    40 => 41 => 42 => 43 => 44 => 45 => 46 => 47 => 48 => 49 => 50 => 51 => 52 =>
+                    53 => 54 => 55 => 56 => 57 => 58 => 59 => 60 => 61 => 62 => 63 => 64 => 65 =>
+                    66 => 67 => 68 => 69 => 70 => 71 => 72 => 73 => 74 => 75 => 76 => 77 => 78 =>
+                    79 => 80 => 81 => 82 => 83 => 84 => 85 => 86 => 87 => 88 => 89 => 90 => 91 =>
+                    92 => 93 => 94 => 95 => 96 => 97 => 98 => 99 => 100 => 101 => 102 => 103 =>
+                    104 => 105 => 106 => 107 => 108 => 109 => 110 => 111 => 112 => 113 => 114 =>
+                    115 => 116 => 117 => 118 => 119 => 120 => 121 => 122 => 123 => 124 => 125 =>
+                    126 => 127 => 128 => 129 => 130 => 131 => 132 => 133 => 134 => 135 => 136 =>
+                    137 => 138 => 139 => 140 => 141 => 142 => 143 => 144 => 145 => 146 => 147 =>
+                    148 => 149 => 150 => 151 => 152 => 153 => 154 => 155 => 156 => 157 => 158 =>
+                    159 => 160 => 161 => 162 => 163 => 164 => 165 => 166 => 167 => 168 => 169 =>
+                    170 => 171 => 172 => 173 => 174 => 175 => 176 => 177 => 178 => 179 => 180 =>
+                    181 => 182 => 183 => 184 => 185 => 186 => 187 => 188 => 189 => 190 => 191 =>
+                    192 => 193 => 194 => 195 => 196 => 197 => 198 => 199 => 200 => 201 => 202 =>
+                    203 => 204 => 205 => 206 => 207 => 208 => 209 => 210 => 211 => 212 => 213 =>
+                    214 => 215 => 216 => 217 => 218 => 219 => 220 => 221 => 222 => 223 => 224 =>
+                    225 => 226 => 227 => 228 => 229 => 230 => 231 => 232 => 233 => 234 => 235 =>
+                    236 => 237 => 238 => 239 => 240 => 241 => 242 => 243 => 244 => 245 => 246 =>
+                    247 => 248 => 249 => 250 => 251 => 252 => 253 => 254 => 255 => 256 => 257 =>
+                    258 => 259 => 260 => 261 => 262 => 263 => 264 => 265 => 266 => 267 => 268 =>
+                    269 => 270 => 271 => 272 => 273 => 274 => 275 => 276 => 277 => 278 => 279 =>
+                    280 => 281 => 282 => 283 => 284 => 285 => 286 => 287 => 288 => 289 => 290 =>
+                    291 => 292 => 293 => 294 => 295 => 296 => 297 => 298 => 299 => 300 => 301 =>
+                    302 => 303 => 304 => 305 => 306 => 307 => 308 => 309 => 310 => 311 => 312 =>
+                    313 => 314 => 315 => 316 => 317 => 318 => 319 => 320 => 321 => 322 => 323 =>
+                    324 => 325 => 326 => 327 => 328 => 329 => 330 => 331 => 332 => 333 => 334 =>
+                    335 => 336 => 337 => 338 => 339 => 340 => 341 => 342 => 343 => 344 => 345 =>
+                    346 => 347 => 348 => 349 => 350 => 351 => 352 => 353 => 354 => 355 => 356 =>
+                    357 => 358 => 359 => 360 => 361 => 362 => 363 => 364 => 365 => 366 => 367 =>
+                    368 => 369 => 370 => 371 => 372 => 373 => 374 => 375 => 376 => 377 => 378 =>
+                    379 => 380 => 381 => 382 => 383 => 384 => 385 => 386 => 387 => 388 => 389 =>
+                    390 => 391 => 392 => 393 => 394 => 395 => 396 => 397 => 398 => 399
+                ]
+            )
+            .unwrap();
+
+            assert_eq!(g.edge_count(), 399);
+            assert_eq!(g.longest_chain().len(), 400);
+        }
+
         #[test]
         #[should_panic(expected = "EdgeContainsNonexistentNode { node: 10 }")]
         fn outcome_node_not_exist() {
@@ -779,5 +2261,133 @@ mod tests {
 
             PrecedenceGraph::new(nodes, edges).unwrap();
         }
+
+        #[test]
+        #[should_panic(expected = "SelfLoop { node: 3 }")]
+        fn self_loop() {
+            let mut nodes = HashSet::new();
+            nodes.insert(1);
+            nodes.insert(2);
+            nodes.insert(3);
+
+            let mut edges = HashSet::new();
+            edges.insert((1, 2));
+            edges.insert((3, 3));
+
+            PrecedenceGraph::new(nodes, edges).unwrap();
+        }
+
+        #[test]
+        fn cycle_detected() {
+            let err = create_graph!(
+                Nodes: [],
+                Edges: [1 => 2, 2 => 3, 3 => 4, 4 => 2, 4 => 5]
+            )
+            .unwrap_err();
+
+            let PrecedenceGraphError::CycleDetected { cycle } = err else {
+                panic!("expected cycle, got {err:?}");
+            };
+
+            let start = cycle.iter().position(|&node| node == 2).unwrap();
+            let cycle = [&cycle[start..], &cycle[..start]].concat();
+
+            assert_eq!(cycle, vec![2, 3, 4]);
+
+            let err = create_graph!(
+                Nodes: [],
+                Edges: [1 => 2, 2 => 1]
+            )
+            .unwrap_err();
+
+            assert!(matches!(
+                err,
+                PrecedenceGraphError::CycleDetected { cycle } if cycle.len() == 2
+            ));
+        }
+
+        #[test]
+        fn send_sync() {
+            fn assert_send_sync<T: Send + Sync>() {}
+
+            assert_send_sync::<PrecedenceGraph>();
+            assert_send_sync::<PrecedenceGraph<String>>();
+        }
+
+        #[test]
+        fn shared_between_threads() {
+            let g = std::sync::Arc::new(
+                create_graph!(
+                    Nodes: [],
+                    Edges: [1 => 2, 2 => 3]
+                )
+                .unwrap(),
+            );
+
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    let g = std::sync::Arc::clone(&g);
+                    std::thread::spawn(move || g.gc_list())
+                })
+                .collect();
+
+            for handle in handles {
+                assert_eq!(handle.join().unwrap(), vec![1, 2, 3]);
+            }
+        }
+
+        #[test]
+        fn string_nodes() {
+            let g = create_graph!(
+                Nodes: [],
+                Edges: ["fetch" => "build", "build" => "test", "build" => "package"]
+            )
+            .unwrap();
+
+            let res = g.msf_list();
+
+            assert_eq!(res[0], "fetch");
+            assert_eq!(res[1], "build");
+            assert!(matches!(res[2], "test" | "package"));
+            assert!(matches!(res[3], "test" | "package"));
+
+            let res = g.gc_schedule(&[1, 1, 2]);
+
+            assert_eq!(res[0], [Some("fetch")]);
+            assert_eq!(res[1], [Some("build")]);
+            assert!(matches!(
+                res[2][..],
+                [Some("test"), Some("package")] | [Some("package"), Some("test")]
+            ));
+        }
+
+        #[test]
+        fn newtype_nodes() {
+            #[derive(Hash, PartialEq, Eq, Clone, Debug)]
+            struct JobId(u32);
+
+            let mut nodes = HashSet::new();
+            nodes.insert(JobId(1));
+            nodes.insert(JobId(2));
+
+            let mut edges = HashSet::new();
+            edges.insert((JobId(1), JobId(2)));
+
+            let g = PrecedenceGraph::new(nodes, edges).unwrap();
+
+            assert_eq!(g.gc_list(), vec![JobId(1), JobId(2)]);
+        }
+
+        #[test]
+        #[should_panic(expected = "EdgeContainsNonexistentNode { node: \"missing\" }")]
+        fn string_node_not_exist() {
+            let mut nodes = HashSet::new();
+            nodes.insert("build");
+
+            let mut edges = HashSet::new();
+            edges.insert(("build", "missing"));
+
+            PrecedenceGraph::new(nodes, edges).unwrap();
+        }
     }
 }
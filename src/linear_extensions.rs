@@ -0,0 +1,243 @@
+use std::{collections::HashMap, hash::Hash};
+
+use rand::Rng;
+
+use crate::precedence_graph::PrecedenceGraph;
+
+/// Set of node indices closed under predecessors, as a bitset.
+type DownSet = Vec<u64>;
+
+/// Depth-first enumeration of topological orders, one node is placed per step.
+struct LinearExtensions<'a, N, P> {
+    graph: &'a PrecedenceGraph<N, P>,
+    in_degree: Vec<usize>,
+    placed: Vec<bool>,
+    order: Vec<usize>,
+    /// Candidates at every placed position with the index of the next one to try.
+    frames: Vec<(Vec<usize>, usize)>,
+    started: bool,
+}
+
+impl<'a, N: Hash + Eq + Clone, P> LinearExtensions<'a, N, P> {
+    fn new(graph: &'a PrecedenceGraph<N, P>) -> Self {
+        Self {
+            graph,
+            in_degree: graph.nodes.iter().map(|node| node.pred.len()).collect(),
+            placed: vec![false; graph.nodes.len()],
+            order: Vec::with_capacity(graph.nodes.len()),
+            frames: vec![],
+            started: false,
+        }
+    }
+
+    fn place(&mut self, node: usize) {
+        self.order.push(node);
+        self.placed[node] = true;
+
+        for &succ in &self.graph.nodes[node].succ {
+            self.in_degree[succ] -= 1;
+        }
+    }
+
+    fn unplace(&mut self) {
+        let node = self.order.pop().expect("placed order should not be empty");
+        self.placed[node] = false;
+
+        for &succ in &self.graph.nodes[node].succ {
+            self.in_degree[succ] += 1;
+        }
+    }
+
+    /// Places the first candidate at every position until the order is complete.
+    fn descend(&mut self) {
+        while self.order.len() < self.graph.nodes.len() {
+            let candidates: Vec<usize> = (0..self.graph.nodes.len())
+                .filter(|&node| !self.placed[node] && self.in_degree[node] == 0)
+                .collect();
+
+            self.place(candidates[0]);
+            self.frames.push((candidates, 1));
+        }
+    }
+
+    /// Replaces the deepest node which still has untried candidates.
+    fn advance(&mut self) -> bool {
+        while let Some((candidates, next)) = self.frames.pop() {
+            self.unplace();
+
+            if let Some(&node) = candidates.get(next) {
+                self.place(node);
+                self.frames.push((candidates, next + 1));
+
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+impl<N: Hash + Eq + Clone, P> Iterator for LinearExtensions<'_, N, P> {
+    type Item = Vec<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+        } else if !self.advance() {
+            return None;
+        }
+
+        self.descend();
+
+        Some(self.graph.values(self.order.iter().copied()))
+    }
+}
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    /// Every order of the nodes which respects the edges, produced lazily.
+    pub fn linear_extensions(&self) -> impl Iterator<Item = Vec<N>> + '_ {
+        LinearExtensions::new(self)
+    }
+
+    /// Number of linear extensions, `None` if it does not fit in `u128`.
+    pub fn count_linear_extensions(&self) -> Option<u128> {
+        let mut counts = HashMap::from([(self.empty_down_set(), 1u128)]);
+
+        for _ in 0..self.nodes.len() {
+            let mut next_counts = HashMap::new();
+
+            for (down_set, count) in counts {
+                for node in self.addable(&down_set) {
+                    let entry = next_counts
+                        .entry(Self::with_node(&down_set, node))
+                        .or_insert(0u128);
+                    *entry = entry.checked_add(count)?;
+                }
+            }
+
+            counts = next_counts;
+        }
+
+        counts.into_values().next()
+    }
+
+    /// Linear extension drawn uniformly at random, `None` if the number of linear extensions
+    /// does not fit in `u128`.
+    ///
+    /// Every down-set of the order is built before drawing, so time and memory grow
+    /// exponentially with the width. Wide orders are better served by
+    /// [`sample_linear_extension_approx`](Self::sample_linear_extension_approx).
+    pub fn sample_linear_extension(&self, rng: &mut impl Rng) -> Option<Vec<N>> {
+        let layers = self.down_set_layers();
+
+        // Number of ways to complete every down-set to a linear extension.
+        let (full, rest) = layers
+            .split_last()
+            .expect("layers should contain empty down-set");
+        let mut completions: HashMap<DownSet, u128> = HashMap::from([(full[0].clone(), 1)]);
+
+        for layer in rest.iter().rev() {
+            for down_set in layer {
+                let mut count = 0u128;
+
+                for node in self.addable(down_set) {
+                    count = count.checked_add(completions[&Self::with_node(down_set, node)])?;
+                }
+
+                completions.insert(down_set.clone(), count);
+            }
+        }
+
+        let mut down_set = self.empty_down_set();
+        let mut order = Vec::with_capacity(self.nodes.len());
+
+        while order.len() < self.nodes.len() {
+            let mut choice = rng.gen_range(0..completions[&down_set]);
+
+            let node = self
+                .addable(&down_set)
+                .find(|&node| {
+                    let count = completions[&Self::with_node(&down_set, node)];
+
+                    if choice < count {
+                        return true;
+                    }
+
+                    choice -= count;
+                    false
+                })
+                .expect("choice should be below the total of completions");
+
+            down_set = Self::with_node(&down_set, node);
+            order.push(node);
+        }
+
+        Some(self.values(order))
+    }
+
+    /// Linear extension drawn approximately uniformly at random by a Markov chain of `steps`
+    /// adjacent transpositions, starting from a topological order.
+    ///
+    /// Each step picks a random adjacent pair and swaps it with probability 1/2 if no edge
+    /// joins the two nodes. The chain converges to the uniform distribution and is close to it
+    /// after about `n³ log n` steps for `n` nodes (Bubley and Dyer). A step costs the degree of
+    /// a node, so wide orders stay cheap, unlike with
+    /// [`sample_linear_extension`](Self::sample_linear_extension).
+    pub fn sample_linear_extension_approx(&self, rng: &mut impl Rng, steps: usize) -> Vec<N> {
+        let mut order = self.topological_order();
+
+        if order.len() > 1 {
+            for _ in 0..steps {
+                let position = rng.gen_range(0..order.len() - 1);
+                let (first, second) = (order[position], order[position + 1]);
+
+                if rng.gen_bool(0.5) && !self.nodes[first].succ.contains(&second) {
+                    order.swap(position, position + 1);
+                }
+            }
+        }
+
+        self.values(order)
+    }
+
+    /// Down-sets grouped by their size.
+    fn down_set_layers(&self) -> Vec<Vec<DownSet>> {
+        let mut layers = vec![vec![self.empty_down_set()]];
+
+        for size in 0..self.nodes.len() {
+            let mut next: Vec<DownSet> = layers[size]
+                .iter()
+                .flat_map(|down_set| {
+                    self.addable(down_set)
+                        .map(|node| Self::with_node(down_set, node))
+                })
+                .collect();
+
+            next.sort_unstable();
+            next.dedup();
+            layers.push(next);
+        }
+
+        layers
+    }
+
+    fn empty_down_set(&self) -> DownSet {
+        vec![0; self.nodes.len().div_ceil(64)]
+    }
+
+    /// Nodes outside the down-set whose predecessors are all in it.
+    fn addable<'a>(&'a self, down_set: &'a DownSet) -> impl Iterator<Item = usize> + 'a {
+        let contains = |node: usize| down_set[node / 64] & (1 << (node % 64)) != 0;
+
+        (0..self.nodes.len()).filter(move |&node| {
+            !contains(node) && self.nodes[node].pred.iter().all(|&pred| contains(pred))
+        })
+    }
+
+    fn with_node(down_set: &DownSet, node: usize) -> DownSet {
+        let mut down_set = down_set.clone();
+        down_set[node / 64] |= 1 << (node % 64);
+
+        down_set
+    }
+}
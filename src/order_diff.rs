@@ -0,0 +1,53 @@
+use std::hash::Hash;
+
+use crate::precedence_graph::PrecedenceGraph;
+
+/// Precedences which differ between two graphs, direct or implied by paths.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OrderDiff<N = u128> {
+    /// Precedences of the other graph which this graph lacks.
+    pub added: Vec<(N, N)>,
+    /// Precedences of this graph which the other graph lacks.
+    pub removed: Vec<(N, N)>,
+}
+
+impl<N> OrderDiff<N> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    /// Whether both graphs have the same nodes and the same transitive closure.
+    pub fn same_order_as<Q>(&self, other: &PrecedenceGraph<N, Q>) -> bool {
+        self.node_count() == other.node_count()
+            && self.nodes().all(|node| other.contains_node(node))
+            && self.order_diff(other).is_empty()
+    }
+
+    /// Whether every precedence of the other graph holds in this one.
+    pub fn is_refinement_of<Q>(&self, other: &PrecedenceGraph<N, Q>) -> bool {
+        other.precedences().all(|(a, b)| self.reaches(a, b))
+    }
+
+    /// Precedences to add to and remove from this graph's order to get the other one.
+    pub fn order_diff<Q>(&self, other: &PrecedenceGraph<N, Q>) -> OrderDiff<N> {
+        OrderDiff {
+            added: Self::missing(other, self),
+            removed: Self::missing(self, other),
+        }
+    }
+
+    /// Pairs of nodes where the first one reaches the second one.
+    fn precedences(&self) -> impl Iterator<Item = (&N, &N)> + '_ {
+        self.nodes()
+            .flat_map(move |node| self.descendants(node).map(move |desc| (node, desc)))
+    }
+
+    fn missing<Q, R>(from: &PrecedenceGraph<N, Q>, to: &PrecedenceGraph<N, R>) -> Vec<(N, N)> {
+        from.precedences()
+            .filter(|(a, b)| !to.reaches(a, b))
+            .map(|(a, b)| (a.clone(), b.clone()))
+            .collect()
+    }
+}
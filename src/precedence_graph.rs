@@ -1,221 +1,443 @@
 use std::{
-    cell::RefCell,
     collections::{HashMap, HashSet},
-    rc::Rc,
+    hash::Hash,
+    sync::OnceLock,
 };
 
 use itertools::Itertools;
 use thiserror::Error;
 
-use crate::node::Node;
+use crate::{node::Node, reachability::Reachability};
 
 #[derive(Error, Debug)]
-pub enum PrecedenceGraphError {
-    #[error("Edge contains nonexistent node: {node}")]
-    EdgeContainsNonexistentNode { node: u128 },
+pub enum PrecedenceGraphError<N = u128> {
+    #[error("Edge contains nonexistent node: {node:?}")]
+    EdgeContainsNonexistentNode { node: N },
+    #[error("Edge connects node {node:?} to itself")]
+    SelfLoop { node: N },
+    #[error("Edges form a cycle: {cycle:?}")]
+    CycleDetected { cycle: Vec<N> },
+    #[error("Node {node:?} is present in more than one graph")]
+    DuplicateNode { node: N },
 }
 
-#[derive(PartialEq, Eq, Debug)]
-pub struct PrecedenceGraph {
-    nodes: HashSet<u128>,
-    graph: HashMap<u128, Rc<RefCell<Node>>>,
+#[derive(Clone, Debug)]
+pub struct PrecedenceGraph<N = u128, P = ()> {
+    pub(crate) nodes: Vec<Node<N, P>>,
+    pub(crate) indices: HashMap<N, usize>,
+    pub(crate) reachability: OnceLock<Reachability>,
 }
 
-impl PrecedenceGraph {
-    pub fn new(
-        nodes: HashSet<u128>,
-        edges: HashSet<(u128, u128)>,
-    ) -> Result<Self, PrecedenceGraphError> {
-        let mut graph = HashMap::new();
-
-        for (outcome, income) in &edges {
-            if !(nodes.contains(outcome) && nodes.contains(income)) {
-                return if !nodes.contains(outcome) {
-                    Err(PrecedenceGraphError::EdgeContainsNonexistentNode { node: *outcome })
-                } else {
-                    Err(PrecedenceGraphError::EdgeContainsNonexistentNode { node: *income })
+impl<N, P> Default for PrecedenceGraph<N, P> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            indices: HashMap::new(),
+            reachability: OnceLock::new(),
+        }
+    }
+}
+
+impl<N: Hash + Eq, P: PartialEq> PartialEq for PrecedenceGraph<N, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes.len() == other.nodes.len()
+            && self.nodes.iter().all(|node| {
+                let Some(&other_index) = other.indices.get(&node.val) else {
+                    return false;
                 };
+                let other_node = &other.nodes[other_index];
+
+                let succ: HashSet<&N> = node.succ.iter().map(|&s| &self.nodes[s].val).collect();
+                let other_succ: HashSet<&N> = other_node
+                    .succ
+                    .iter()
+                    .map(|&s| &other.nodes[s].val)
+                    .collect();
+
+                node.payload == other_node.payload && succ == other_succ
+            })
+    }
+}
+
+impl<N: Hash + Eq, P: Eq> Eq for PrecedenceGraph<N, P> {}
+
+impl<N: Hash + Eq + Clone> PrecedenceGraph<N> {
+    pub fn new(nodes: HashSet<N>, edges: HashSet<(N, N)>) -> Result<Self, PrecedenceGraphError<N>> {
+        Self::with_payloads(nodes.into_iter().map(|node| (node, ())).collect(), edges)
+    }
+}
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    pub fn with_payloads(
+        nodes: HashMap<N, P>,
+        edges: HashSet<(N, N)>,
+    ) -> Result<Self, PrecedenceGraphError<N>> {
+        let mut graph = Self::default();
+
+        for (node, payload) in nodes {
+            graph.add_node_with_payload(node, payload);
+        }
+
+        for (outcome, income) in edges {
+            let (out_index, in_index) =
+                match (graph.indices.get(&outcome), graph.indices.get(&income)) {
+                    (Some(&out_index), Some(&in_index)) => (out_index, in_index),
+                    (None, _) => {
+                        return Err(PrecedenceGraphError::EdgeContainsNonexistentNode {
+                            node: outcome,
+                        })
+                    }
+                    (_, None) => {
+                        return Err(PrecedenceGraphError::EdgeContainsNonexistentNode {
+                            node: income,
+                        })
+                    }
+                };
+
+            if out_index == in_index {
+                return Err(PrecedenceGraphError::SelfLoop { node: outcome });
             }
 
-            let in_node = graph
-                .get(income)
-                .cloned()
-                .unwrap_or(Rc::new(RefCell::new(Node {
-                    val: *income,
-                    succ: vec![],
-                    pred: vec![],
-                })));
-
-            let out_node = graph
-                .get(outcome)
-                .cloned()
-                .unwrap_or(Rc::new(RefCell::new(Node {
-                    val: *outcome,
-                    succ: vec![],
-                    pred: vec![],
-                })));
-
-            out_node.borrow_mut().succ.push(Rc::clone(&in_node));
-            in_node.borrow_mut().pred.push(Rc::clone(&out_node));
-
-            graph.insert(*income, in_node);
-            graph.insert(*outcome, out_node);
+            graph.push_edge(out_index, in_index);
         }
 
-        Ok(Self { nodes, graph })
+        if let Some(cycle) = graph.find_cycle() {
+            return Err(PrecedenceGraphError::CycleDetected {
+                cycle: graph.values(cycle),
+            });
+        }
+
+        Ok(graph)
     }
 
-    pub fn is_linear_order(&self) -> bool {
-        let node = match self.graph.iter().next() {
-            Some((_id, node)) => node,
-            None => return true,
-        };
+    pub fn add_node(&mut self, node: N) -> bool
+    where
+        P: Default,
+    {
+        self.add_node_with_payload(node, P::default())
+    }
+
+    /// Adds the node with its payload, an already present node keeps its old payload.
+    pub fn add_node_with_payload(&mut self, node: N, payload: P) -> bool {
+        if self.indices.contains_key(&node) {
+            return false;
+        }
+
+        self.indices.insert(node.clone(), self.nodes.len());
+        self.nodes.push(Node::new(node, payload));
+        self.reachability.take();
 
-        self.nodes.len() == node.borrow().comparable().count()
+        true
     }
 
-    pub fn is_first(&self) -> bool {
-        for i in self.graph.values() {
-            for j in self.graph.values() {
-                let i = i.borrow();
-                let j = j.borrow();
+    pub fn payload(&self, node: &N) -> Option<&P> {
+        self.indices
+            .get(node)
+            .map(|&index| &self.nodes[index].payload)
+    }
+
+    pub fn payload_mut(&mut self, node: &N) -> Option<&mut P> {
+        self.indices
+            .get(node)
+            .map(|&index| &mut self.nodes[index].payload)
+    }
+
+    /// Removes the node together with all its incident edges.
+    pub fn remove_node(&mut self, node: &N) -> bool {
+        let Some(index) = self.indices.remove(node) else {
+            return false;
+        };
+
+        self.reachability.take();
+
+        for succ in std::mem::take(&mut self.nodes[index].succ) {
+            self.nodes[succ].pred.retain(|&pred| pred != index);
+        }
+
+        for pred in std::mem::take(&mut self.nodes[index].pred) {
+            self.nodes[pred].succ.retain(|&succ| succ != index);
+        }
+
+        let last = self.nodes.len() - 1;
+        self.nodes.swap_remove(index);
 
-                let succ_i: HashSet<Node> = i.all_successors().collect();
-                let succ_j: HashSet<Node> = j.all_successors().collect();
+        if index != last {
+            let moved = &self.nodes[index];
+            *self
+                .indices
+                .get_mut(&moved.val)
+                .expect("moved node should exist") = index;
 
-                if succ_i.is_subset(&succ_j) || succ_j.is_subset(&succ_i) {
-                    continue;
+            for succ in moved.succ.clone() {
+                for pred in &mut self.nodes[succ].pred {
+                    if *pred == last {
+                        *pred = index;
+                    }
                 }
+            }
 
-                if !i.is_succ_linear_order_without(&succ_j) {
-                    return true;
+            for pred in self.nodes[index].pred.clone() {
+                for succ in &mut self.nodes[pred].succ {
+                    if *succ == last {
+                        *succ = index;
+                    }
                 }
             }
         }
 
-        false
+        true
     }
 
-    pub fn is_second(&self) -> bool {
-        for i in self.graph.values() {
-            for j in self.graph.values() {
-                let i = i.borrow();
-                let j = j.borrow();
+    /// Adds an edge between existing nodes, returns `false` if the edge is already present.
+    pub fn add_edge(&mut self, outcome: N, income: N) -> Result<bool, PrecedenceGraphError<N>> {
+        let (out_index, in_index) = match (self.indices.get(&outcome), self.indices.get(&income)) {
+            (Some(&out_index), Some(&in_index)) => (out_index, in_index),
+            (None, _) => {
+                return Err(PrecedenceGraphError::EdgeContainsNonexistentNode { node: outcome })
+            }
+            (_, None) => {
+                return Err(PrecedenceGraphError::EdgeContainsNonexistentNode { node: income })
+            }
+        };
 
-                let succ_i: HashSet<Node> = i.all_successors().collect();
-                let succ_j: HashSet<Node> = j.all_successors().collect();
+        if out_index == in_index {
+            return Err(PrecedenceGraphError::SelfLoop { node: outcome });
+        }
 
-                for x in succ_i.difference(&succ_j) {
-                    for z in succ_j.difference(&succ_i) {
-                        let z_all_pred: HashSet<Node> = z.all_predecessor().collect();
+        if self.nodes[out_index].succ.contains(&in_index) {
+            return Ok(false);
+        }
 
-                        for y in x.all_predecessor().filter(|node| !i.is_comparable(node)) {
-                            if !z_all_pred.contains(&y) {
-                                return true;
-                            }
-                        }
-                    }
-                }
-            }
+        // The closure is dropped after every edge, so only consult it when it is still cached.
+        let path = match self.reachability.get() {
+            Some(reachability) if !reachability.reaches(in_index, out_index) => None,
+            _ => self.find_path(in_index, out_index),
+        };
+
+        if let Some(path) = path {
+            return Err(PrecedenceGraphError::CycleDetected {
+                cycle: self.values(path),
+            });
         }
 
-        false
+        self.push_edge(out_index, in_index);
+
+        Ok(true)
     }
 
-    pub fn is_third(&self) -> bool {
-        for i in self.graph.values() {
-            for j in self.graph.values() {
-                let i = i.borrow();
-                let j = j.borrow();
+    pub fn remove_edge(&mut self, outcome: &N, income: &N) -> bool {
+        let (Some(&out_index), Some(&in_index)) =
+            (self.indices.get(outcome), self.indices.get(income))
+        else {
+            return false;
+        };
 
-                let succ_i: HashSet<Node> = i.all_successors().collect();
-                let succ_j: HashSet<Node> = j.all_successors().collect();
+        let Some(position) = self.nodes[out_index]
+            .succ
+            .iter()
+            .position(|&succ| succ == in_index)
+        else {
+            return false;
+        };
 
-                for x in succ_i.difference(&succ_j) {
-                    for z in succ_j.difference(&succ_i) {
-                        let z_all_succ: HashSet<Node> = z.all_successors().collect();
+        self.nodes[out_index].succ.swap_remove(position);
+        self.nodes[in_index].pred.retain(|&pred| pred != out_index);
+        self.reachability.take();
 
-                        for y in x.all_successors().filter(|node| succ_j.contains(node)) {
-                            if !z_all_succ.contains(&y) {
-                                return true;
-                            }
-                        }
-                    }
-                }
-            }
+        true
+    }
+
+    pub fn contains_edge(&self, outcome: &N, income: &N) -> bool {
+        match (self.indices.get(outcome), self.indices.get(income)) {
+            (Some(&out_index), Some(&in_index)) => self.nodes[out_index].succ.contains(&in_index),
+            _ => false,
         }
+    }
+
+    pub fn contains_node(&self, node: &N) -> bool {
+        self.indices.contains_key(node)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> + '_ {
+        self.nodes.iter().map(|node| &node.val)
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (&N, &N)> + '_ {
+        self.nodes.iter().flat_map(move |node| {
+            node.succ
+                .iter()
+                .map(move |&succ| (&node.val, &self.nodes[succ].val))
+        })
+    }
+
+    /// Direct successors of the node, empty for a nonexistent node.
+    pub fn successors(&self, node: &N) -> impl Iterator<Item = &N> + '_ {
+        self.neighbours(node, |node| &node.succ)
+    }
+
+    /// Direct predecessors of the node, empty for a nonexistent node.
+    pub fn predecessors(&self, node: &N) -> impl Iterator<Item = &N> + '_ {
+        self.neighbours(node, |node| &node.pred)
+    }
+
+    pub fn in_degree(&self, node: &N) -> Option<usize> {
+        self.indices
+            .get(node)
+            .map(|&index| self.nodes[index].pred.len())
+    }
+
+    pub fn out_degree(&self, node: &N) -> Option<usize> {
+        self.indices
+            .get(node)
+            .map(|&index| self.nodes[index].succ.len())
+    }
 
-        false
+    /// Nodes without predecessors.
+    pub fn sources(&self) -> impl Iterator<Item = &N> + '_ {
+        self.nodes
+            .iter()
+            .filter(|node| node.pred.is_empty())
+            .map(|node| &node.val)
     }
 
-    pub fn is_quasi_interval_order(&self) -> bool {
-        !(self.is_first() || self.is_second() || self.is_third())
+    /// Nodes without successors.
+    pub fn sinks(&self) -> impl Iterator<Item = &N> + '_ {
+        self.nodes
+            .iter()
+            .filter(|node| node.succ.is_empty())
+            .map(|node| &node.val)
     }
 
-    pub fn msf_list(&self) -> Vec<u128> {
-        self.graph
-            .values()
-            .map(|node| node.borrow())
-            .sorted_by(|a, b| b.all_successors().count().cmp(&a.all_successors().count()))
-            .map(|node| node.val)
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.nodes.iter().map(|node| node.succ.len()).sum()
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        node: &N,
+        next: impl Fn(&Node<N, P>) -> &Vec<usize> + 'a,
+    ) -> impl Iterator<Item = &'a N> + 'a {
+        self.indices
+            .get(node)
+            .map(move |&index| next(&self.nodes[index]).iter())
+            .into_iter()
+            .flatten()
+            .map(|&node| &self.nodes[node].val)
+    }
+
+    pub fn is_linear_order(&self) -> bool {
+        let reachability = self.reachability();
+
+        (0..self.nodes.len())
+            .map(|node| reachability.descendants(node).len())
+            .sum::<usize>()
+            == self.nodes.len() * self.nodes.len().saturating_sub(1) / 2
+    }
+
+    pub fn msf_list(&self) -> Vec<N> {
+        let reachability = self.reachability();
+
+        (0..self.nodes.len())
+            .map(|node| (node, reachability.descendants(node).len()))
+            .sorted_by(|a, b| b.1.cmp(&a.1))
+            .map(|(node, _)| self.nodes[node].val.clone())
             .collect()
     }
 
-    pub fn gc_list(&self) -> Vec<u128> {
+    pub fn gc_list(&self) -> Vec<N> {
         let mut j = 0;
         let n = self.nodes.len();
 
-        let mut labels: HashMap<u128, usize> = HashMap::with_capacity(n);
-        let mut list = vec![0; n];
+        let mut labels: Vec<Option<usize>> = vec![None; n];
+        let mut list = Vec::with_capacity(n);
 
         while j < n {
             let node = self
-                .graph
-                .values()
-                .map(|node| node.borrow())
-                .filter(|node| {
-                    !labels.contains_key(&node.val)
-                        && node
-                            .succ
-                            .iter()
-                            .all(|succ| labels.contains_key(&succ.borrow().val))
+                .nodes
+                .iter()
+                .enumerate()
+                .filter(|(index, node)| {
+                    labels[*index].is_none() && node.succ.iter().all(|&succ| labels[succ].is_some())
                 })
-                .map(|node| {
+                .map(|(index, node)| {
                     let successors = node
                         .succ
                         .iter()
-                        .map(|succ| {
-                            labels
-                                .get(&succ.borrow().val)
-                                .expect("node should be labelled")
-                        })
+                        .map(|&succ| labels[succ].expect("node should be labelled"))
                         .sorted_by(|a, b| b.cmp(a))
                         .collect::<Vec<_>>();
 
-                    (node, successors)
+                    (index, successors)
                 })
                 .min_by(|a, b| a.1.cmp(&b.1))
                 .expect("iterator should have at least one node")
                 .0;
 
-            labels.insert(node.val, j);
-            list[n - j - 1] = node.val;
+            labels[node] = Some(j);
+            list.push(self.nodes[node].val.clone());
 
             j += 1;
         }
 
+        list.reverse();
+
         list
     }
 
-    pub fn gc_schedule(&self, profile: &[usize]) -> Vec<Vec<Option<u128>>> {
+    pub fn gc_schedule(&self, profile: &[usize]) -> Vec<Vec<Option<N>>> {
         self.schedule(self.gc_list(), profile)
     }
 
-    pub fn msf_schedule(&self, profile: &[usize]) -> Vec<Vec<Option<u128>>> {
+    pub fn msf_schedule(&self, profile: &[usize]) -> Vec<Vec<Option<N>>> {
         self.schedule(self.msf_list(), profile)
     }
 
-    fn schedule(&self, list: Vec<u128>, m: &[usize]) -> Vec<Vec<Option<u128>>> {
+    pub fn msf_list_with_payloads(&self) -> Vec<(N, &P)> {
+        self.with_payloads_list(self.msf_list())
+    }
+
+    pub fn gc_list_with_payloads(&self) -> Vec<(N, &P)> {
+        self.with_payloads_list(self.gc_list())
+    }
+
+    pub fn gc_schedule_with_payloads(&self, profile: &[usize]) -> Vec<Vec<Option<(N, &P)>>> {
+        self.with_payloads_schedule(self.gc_schedule(profile))
+    }
+
+    pub fn msf_schedule_with_payloads(&self, profile: &[usize]) -> Vec<Vec<Option<(N, &P)>>> {
+        self.with_payloads_schedule(self.msf_schedule(profile))
+    }
+
+    fn with_payloads_list(&self, list: Vec<N>) -> Vec<(N, &P)> {
+        list.into_iter()
+            .map(|node| {
+                let payload = &self.nodes[self.index(&node)].payload;
+                (node, payload)
+            })
+            .collect()
+    }
+
+    fn with_payloads_schedule(&self, schedule: Vec<Vec<Option<N>>>) -> Vec<Vec<Option<(N, &P)>>> {
+        schedule
+            .into_iter()
+            .map(|slot| {
+                slot.into_iter()
+                    .map(|node| {
+                        node.map(|node| {
+                            let payload = &self.nodes[self.index(&node)].payload;
+                            (node, payload)
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn schedule(&self, list: Vec<N>, m: &[usize]) -> Vec<Vec<Option<N>>> {
         let mut res = Vec::with_capacity(m.len());
 
         let mut task_index = 0;
@@ -226,21 +448,19 @@ impl PrecedenceGraph {
                 continue;
             }
 
-            let mut slot: Vec<Option<u128>> = Vec::with_capacity(n);
-
-            for &i in list.iter().skip(task_index).take(n) {
-                if slot.iter().filter(|&&v| v.is_some()).any(|&v| {
-                    self.graph
-                        .get(&i)
-                        .expect(&format!("Node {} should exist", i))
-                        .borrow()
-                        .all_predecessor()
-                        .map(|v| v.val)
-                        .contains(&v.expect(&format!("Node {:?} should not be option", v)))
-                }) {
+            let mut slot: Vec<Option<N>> = Vec::with_capacity(n);
+
+            for i in list.iter().skip(task_index).take(n) {
+                let i_index = self.index(i);
+
+                if slot
+                    .iter()
+                    .flatten()
+                    .any(|v| self.reachability().reaches(self.index(v), i_index))
+                {
                     break;
                 } else {
-                    slot.push(Some(i));
+                    slot.push(Some(i.clone()));
                     task_index += 1;
                 }
             }
@@ -254,4 +474,132 @@ impl PrecedenceGraph {
 
         res
     }
+
+    pub(crate) fn values(&self, nodes: impl IntoIterator<Item = usize>) -> Vec<N> {
+        nodes
+            .into_iter()
+            .map(|node| self.nodes[node].val.clone())
+            .collect()
+    }
+
+    pub(crate) fn index(&self, node: &N) -> usize {
+        *self.indices.get(node).expect("node should exist")
+    }
+
+    /// Returns nodes of some cycle in edge order, so every node precedes the next one
+    /// and the last node precedes the first one.
+    fn find_cycle(&self) -> Option<Vec<usize>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            OnPath,
+            Done,
+        }
+
+        let mut state = vec![State::New; self.nodes.len()];
+
+        for start in 0..self.nodes.len() {
+            if state[start] != State::New {
+                continue;
+            }
+
+            let mut path = vec![(start, 0)];
+            state[start] = State::OnPath;
+
+            while let Some((node, next)) = path.last_mut() {
+                let node = *node;
+
+                match self.nodes[node].succ.get(*next) {
+                    Some(&succ) => {
+                        *next += 1;
+
+                        match state[succ] {
+                            State::New => {
+                                state[succ] = State::OnPath;
+                                path.push((succ, 0));
+                            }
+                            State::OnPath => {
+                                let begin = path
+                                    .iter()
+                                    .position(|&(node, _)| node == succ)
+                                    .expect("node on path should be in path");
+
+                                return Some(path[begin..].iter().map(|&(node, _)| node).collect());
+                            }
+                            State::Done => {}
+                        }
+                    }
+                    None => {
+                        state[node] = State::Done;
+                        path.pop();
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns nodes of some path of edges from `from` to `to`, both ends included.
+    pub(crate) fn find_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let mut parents = vec![None; self.nodes.len()];
+        let mut stack = vec![from];
+
+        while let Some(node) = stack.pop() {
+            if node == to {
+                let mut path = vec![to];
+                let mut current = to;
+
+                while let Some(parent) = parents[current] {
+                    path.push(parent);
+                    current = parent;
+                }
+
+                path.reverse();
+
+                return Some(path);
+            }
+
+            for &succ in &self.nodes[node].succ {
+                if succ != from && parents[succ].is_none() {
+                    parents[succ] = Some(node);
+                    stack.push(succ);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<N, P> PrecedenceGraph<N, P> {
+    pub(crate) fn push_edge(&mut self, outcome: usize, income: usize) {
+        self.nodes[outcome].succ.push(income);
+        self.nodes[income].pred.push(outcome);
+        self.reachability.take();
+    }
+
+    /// Orders nodes so that every edge goes forward, nodes of cycles are left out.
+    pub(crate) fn topological_order(&self) -> Vec<usize> {
+        let mut in_degree: Vec<usize> = self.nodes.iter().map(|node| node.pred.len()).collect();
+        let mut order: Vec<usize> = (0..self.nodes.len())
+            .filter(|&node| in_degree[node] == 0)
+            .collect();
+
+        let mut next = 0;
+
+        while let Some(&node) = order.get(next) {
+            next += 1;
+
+            for &succ in &self.nodes[node].succ {
+                in_degree[succ] -= 1;
+
+                if in_degree[succ] == 0 {
+                    order.push(succ);
+                }
+            }
+        }
+
+        order
+    }
 }
@@ -0,0 +1,225 @@
+use std::{hash::Hash, ops::ControlFlow};
+
+use crate::{precedence_graph::PrecedenceGraph, reachability::Row};
+
+/// Forbidden configuration showing that a graph is not a quasi-interval order.
+///
+/// `i` and `j` are the nodes whose successor sets are compared, the other nodes are
+/// named as in the definition of the corresponding kind.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum QuasiIntervalViolation<N = u128> {
+    /// Successors of `i` outside successors of `j` branch into `x` and `y`.
+    First { i: N, j: N, x: N, y: N },
+    /// `y` precedes `x` but not `z` and is incomparable with `i`.
+    Second { i: N, j: N, x: N, y: N, z: N },
+    /// `y` succeeds `x` and `j` but not `z`.
+    Third { i: N, j: N, x: N, y: N, z: N },
+}
+
+/// Violation given by node indices, in the order of fields of [`QuasiIntervalViolation`].
+#[derive(Clone, Copy)]
+enum Violation {
+    First([usize; 4]),
+    Second([usize; 5]),
+    Third([usize; 5]),
+}
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    pub fn is_first(&self) -> bool {
+        self.visit_first(&mut |_| ControlFlow::Break(())).is_break()
+    }
+
+    pub fn is_second(&self) -> bool {
+        self.visit_second(&mut |_| ControlFlow::Break(()))
+            .is_break()
+    }
+
+    pub fn is_third(&self) -> bool {
+        self.visit_third(&mut |_| ControlFlow::Break(())).is_break()
+    }
+
+    pub fn is_quasi_interval_order(&self) -> bool {
+        !(self.is_first() || self.is_second() || self.is_third())
+    }
+
+    /// Returns the first forbidden configuration found, checking the kinds in order.
+    pub fn first_quasi_interval_violation(&self) -> Option<QuasiIntervalViolation<N>> {
+        let mut found = None;
+
+        let _ = self.visit_violations(&mut |violation| {
+            found = Some(violation);
+            ControlFlow::Break(())
+        });
+
+        found.map(|violation| self.violation_values(violation))
+    }
+
+    /// Returns every forbidden configuration, empty for a quasi-interval order.
+    pub fn quasi_interval_violations(&self) -> Vec<QuasiIntervalViolation<N>> {
+        let mut found = vec![];
+
+        let _ = self.visit_violations(&mut |violation| {
+            found.push(violation);
+            ControlFlow::Continue(())
+        });
+
+        found
+            .into_iter()
+            .map(|violation| self.violation_values(violation))
+            .collect()
+    }
+
+    fn violation_values(&self, violation: Violation) -> QuasiIntervalViolation<N> {
+        let val = |node: usize| self.nodes[node].val.clone();
+
+        match violation {
+            Violation::First([i, j, x, y]) => QuasiIntervalViolation::First {
+                i: val(i),
+                j: val(j),
+                x: val(x),
+                y: val(y),
+            },
+            Violation::Second([i, j, x, y, z]) => QuasiIntervalViolation::Second {
+                i: val(i),
+                j: val(j),
+                x: val(x),
+                y: val(y),
+                z: val(z),
+            },
+            Violation::Third([i, j, x, y, z]) => QuasiIntervalViolation::Third {
+                i: val(i),
+                j: val(j),
+                x: val(x),
+                y: val(y),
+                z: val(z),
+            },
+        }
+    }
+}
+
+impl<N, P> PrecedenceGraph<N, P> {
+    fn visit_violations(
+        &self,
+        visit: &mut impl FnMut(Violation) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.visit_first(visit)?;
+        self.visit_second(visit)?;
+        self.visit_third(visit)
+    }
+
+    fn visit_first(&self, visit: &mut impl FnMut(Violation) -> ControlFlow<()>) -> ControlFlow<()> {
+        let reachability = self.reachability();
+
+        for i in 0..self.nodes.len() {
+            for j in 0..self.nodes.len() {
+                let succ_i = reachability.descendants(i);
+                let succ_j = reachability.descendants(j);
+
+                if succ_i.is_subset(succ_j) || succ_j.is_subset(succ_i) {
+                    continue;
+                }
+
+                if let Some((x, y)) = self.succ_branch_without(i, succ_j) {
+                    visit(Violation::First([i, j, x, y]))?;
+                }
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    fn visit_second(
+        &self,
+        visit: &mut impl FnMut(Violation) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let reachability = self.reachability();
+
+        for i in 0..self.nodes.len() {
+            for j in 0..self.nodes.len() {
+                let succ_i = reachability.descendants(i);
+                let succ_j = reachability.descendants(j);
+
+                let only_j: Vec<usize> = succ_j.difference(succ_i).collect();
+
+                if only_j.is_empty() {
+                    continue;
+                }
+
+                for x in succ_i.difference(succ_j) {
+                    for &z in &only_j {
+                        let z_all_pred = reachability.ancestors(z);
+
+                        for y in reachability
+                            .ancestors(x)
+                            .iter()
+                            .filter(|&node| !reachability.is_comparable(i, node))
+                        {
+                            if !z_all_pred.contains(y) {
+                                visit(Violation::Second([i, j, x, y, z]))?;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    fn visit_third(&self, visit: &mut impl FnMut(Violation) -> ControlFlow<()>) -> ControlFlow<()> {
+        let reachability = self.reachability();
+
+        for i in 0..self.nodes.len() {
+            for j in 0..self.nodes.len() {
+                let succ_i = reachability.descendants(i);
+                let succ_j = reachability.descendants(j);
+
+                let only_j: Vec<usize> = succ_j.difference(succ_i).collect();
+
+                if only_j.is_empty() {
+                    continue;
+                }
+
+                for x in succ_i.difference(succ_j) {
+                    for &z in &only_j {
+                        let z_all_succ = reachability.descendants(z);
+
+                        for y in reachability
+                            .descendants(x)
+                            .iter()
+                            .filter(|&node| succ_j.contains(node))
+                        {
+                            if !z_all_succ.contains(y) {
+                                visit(Violation::Third([i, j, x, y, z]))?;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Follows successors of the node outside the set while they form a chain and
+    /// returns the first two successors where it branches.
+    fn succ_branch_without(&self, node: usize, set: Row) -> Option<(usize, usize)> {
+        let mut next = Some(node);
+
+        while let Some(node) = next {
+            let mut succ = self.nodes[node]
+                .succ
+                .iter()
+                .copied()
+                .filter(|&v| !set.contains(v));
+
+            next = succ.next();
+
+            if let (Some(x), Some(y)) = (next, succ.next()) {
+                return Some((x, y));
+            }
+        }
+
+        None
+    }
+}
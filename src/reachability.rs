@@ -0,0 +1,154 @@
+use std::hash::Hash;
+
+use crate::{node::Node, precedence_graph::PrecedenceGraph};
+
+/// Transitive closure of a graph stored as bit matrices of descendants and ancestors.
+#[derive(Clone, Debug)]
+pub(crate) struct Reachability {
+    words: usize,
+    descendants: Vec<u64>,
+    ancestors: Vec<u64>,
+}
+
+/// Set of node indices, one row of a [`Reachability`] matrix.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Row<'a>(&'a [u64]);
+
+impl Reachability {
+    /// Computes the closure of an acyclic graph.
+    pub fn new<N, P>(nodes: &[Node<N, P>], order: &[usize]) -> Self {
+        let words = nodes.len().div_ceil(64);
+
+        let mut reachability = Self {
+            words,
+            descendants: vec![0; words * nodes.len()],
+            ancestors: vec![0; words * nodes.len()],
+        };
+
+        for &node in order.iter().rev() {
+            for &succ in &nodes[node].succ {
+                Self::insert(&mut reachability.descendants, words, node, succ);
+                Self::union(&mut reachability.descendants, words, node, succ);
+            }
+        }
+
+        for &node in order {
+            for &pred in &nodes[node].pred {
+                Self::insert(&mut reachability.ancestors, words, node, pred);
+                Self::union(&mut reachability.ancestors, words, node, pred);
+            }
+        }
+
+        reachability
+    }
+
+    pub fn descendants(&self, node: usize) -> Row<'_> {
+        Row(&self.descendants[node * self.words..(node + 1) * self.words])
+    }
+
+    pub fn ancestors(&self, node: usize) -> Row<'_> {
+        Row(&self.ancestors[node * self.words..(node + 1) * self.words])
+    }
+
+    /// Whether there is a nonempty path from `a` to `b`.
+    pub fn reaches(&self, a: usize, b: usize) -> bool {
+        self.descendants(a).contains(b)
+    }
+
+    pub fn is_comparable(&self, a: usize, b: usize) -> bool {
+        a == b || self.reaches(a, b) || self.reaches(b, a)
+    }
+
+    fn insert(matrix: &mut [u64], words: usize, row: usize, column: usize) {
+        matrix[row * words + column / 64] |= 1 << (column % 64);
+    }
+
+    fn union(matrix: &mut [u64], words: usize, row: usize, other: usize) {
+        for word in 0..words {
+            matrix[row * words + word] |= matrix[other * words + word];
+        }
+    }
+}
+
+impl<'a> Row<'a> {
+    pub fn contains(self, node: usize) -> bool {
+        self.0[node / 64] & (1 << (node % 64)) != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_subset(self, other: Row) -> bool {
+        self.0.iter().zip(other.0).all(|(a, b)| a & !b == 0)
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = usize> + 'a {
+        Self::ones(self.0.iter().copied())
+    }
+
+    /// Nodes of this row which are not in the other one.
+    pub fn difference(self, other: Row<'a>) -> impl Iterator<Item = usize> + 'a {
+        Self::ones(self.0.iter().zip(other.0).map(|(a, b)| a & !b))
+    }
+
+    fn ones(words: impl Iterator<Item = u64> + 'a) -> impl Iterator<Item = usize> + 'a {
+        words.enumerate().flat_map(|(index, mut word)| {
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+
+                Some(index * 64 + bit)
+            })
+        })
+    }
+}
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    /// Whether `b` has to be done after `a`, directly or through other nodes.
+    pub fn reaches(&self, a: &N, b: &N) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(&a), Some(&b)) => self.reachability().reaches(a, b),
+            _ => false,
+        }
+    }
+
+    /// Whether the nodes are equal or one of them reaches the other.
+    pub fn is_comparable(&self, a: &N, b: &N) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(&a), Some(&b)) => self.reachability().is_comparable(a, b),
+            _ => false,
+        }
+    }
+
+    /// Nodes reachable from the node, empty for a nonexistent node.
+    pub fn descendants(&self, node: &N) -> impl Iterator<Item = &N> + '_ {
+        self.indices
+            .get(node)
+            .map(|&node| self.reachability().descendants(node).iter())
+            .into_iter()
+            .flatten()
+            .map(|node| &self.nodes[node].val)
+    }
+
+    /// Nodes which reach the node, empty for a nonexistent node.
+    pub fn ancestors(&self, node: &N) -> impl Iterator<Item = &N> + '_ {
+        self.indices
+            .get(node)
+            .map(|&node| self.reachability().ancestors(node).iter())
+            .into_iter()
+            .flatten()
+            .map(|node| &self.nodes[node].val)
+    }
+}
+
+impl<N, P> PrecedenceGraph<N, P> {
+    pub(crate) fn reachability(&self) -> &Reachability {
+        self.reachability
+            .get_or_init(|| Reachability::new(&self.nodes, &self.topological_order()))
+    }
+}
@@ -0,0 +1,150 @@
+use std::hash::Hash;
+
+use crate::{precedence_graph::PrecedenceGraph, reachability::Reachability};
+
+/// Binary decomposition tree of a series-parallel order.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SeriesParallel<N = u128> {
+    Leaf(N),
+    /// Every node of the first part precedes every node of the second one.
+    Series(Box<SeriesParallel<N>>, Box<SeriesParallel<N>>),
+    /// No node of the first part is comparable with a node of the second one.
+    Parallel(Box<SeriesParallel<N>>, Box<SeriesParallel<N>>),
+}
+
+/// Nodes with `a < c`, `b < c` and `b < d` and no other precedence between them.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NShape<N = u128> {
+    pub a: N,
+    pub b: N,
+    pub c: N,
+    pub d: N,
+}
+
+type Composition<N> = fn(Box<SeriesParallel<N>>, Box<SeriesParallel<N>>) -> SeriesParallel<N>;
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    /// Whether the order contains no [`NShape`].
+    pub fn is_series_parallel(&self) -> bool {
+        self.series_parallel_decomposition().is_ok()
+    }
+
+    /// Splits the order into series and parallel compositions down to single nodes,
+    /// `None` stands for the empty graph.
+    pub fn series_parallel_decomposition(&self) -> Result<Option<SeriesParallel<N>>, NShape<N>> {
+        if self.nodes.is_empty() {
+            return Ok(None);
+        }
+
+        let nodes: Vec<usize> = (0..self.nodes.len()).collect();
+
+        match self.decompose(self.reachability(), nodes) {
+            Some(tree) => Ok(Some(tree)),
+            None => Err(self
+                .find_n_shape()
+                .expect("order without decomposition should contain N")),
+        }
+    }
+
+    fn decompose(
+        &self,
+        reachability: &Reachability,
+        nodes: Vec<usize>,
+    ) -> Option<SeriesParallel<N>> {
+        if let [node] = nodes[..] {
+            return Some(SeriesParallel::Leaf(self.nodes[node].val.clone()));
+        }
+
+        let parts = components(&nodes, |a, b| reachability.is_comparable(a, b));
+
+        if parts.len() > 1 {
+            return self.combine(reachability, parts, SeriesParallel::Parallel);
+        }
+
+        let mut parts = components(&nodes, |a, b| !reachability.is_comparable(a, b));
+
+        if parts.len() > 1 {
+            parts.sort_by_key(|part| reachability.ancestors(part[0]).len());
+
+            return self.combine(reachability, parts, SeriesParallel::Series);
+        }
+
+        None
+    }
+
+    fn combine(
+        &self,
+        reachability: &Reachability,
+        parts: Vec<Vec<usize>>,
+        compose: Composition<N>,
+    ) -> Option<SeriesParallel<N>> {
+        let mut trees = parts
+            .into_iter()
+            .map(|part| self.decompose(reachability, part));
+
+        let first = trees.next()??;
+
+        trees.try_fold(first, |tree, next| {
+            Some(compose(Box::new(tree), Box::new(next?)))
+        })
+    }
+
+    fn find_n_shape(&self) -> Option<NShape<N>> {
+        let reachability = self.reachability();
+        let n = self.nodes.len();
+
+        for b in 0..n {
+            for c in reachability.descendants(b).iter() {
+                for a in reachability.ancestors(c).iter() {
+                    if reachability.is_comparable(a, b) {
+                        continue;
+                    }
+
+                    for d in reachability.descendants(b).iter() {
+                        if !reachability.is_comparable(d, c) && !reachability.is_comparable(d, a) {
+                            return Some(NShape {
+                                a: self.nodes[a].val.clone(),
+                                b: self.nodes[b].val.clone(),
+                                c: self.nodes[c].val.clone(),
+                                d: self.nodes[d].val.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Splits nodes into connected components of the graph given by `adjacent`.
+fn components(nodes: &[usize], adjacent: impl Fn(usize, usize) -> bool) -> Vec<Vec<usize>> {
+    let mut visited = vec![false; nodes.len()];
+    let mut components = vec![];
+
+    for start in 0..nodes.len() {
+        if visited[start] {
+            continue;
+        }
+
+        visited[start] = true;
+        let mut stack = vec![start];
+        let mut component = vec![];
+
+        while let Some(current) = stack.pop() {
+            component.push(nodes[current]);
+
+            for next in 0..nodes.len() {
+                if !visited[next] && adjacent(nodes[current], nodes[next]) {
+                    visited[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        components.push(component);
+    }
+
+    components
+}
@@ -0,0 +1,69 @@
+use std::hash::Hash;
+
+use crate::precedence_graph::PrecedenceGraph;
+
+impl<N: Hash + Eq + Clone, P: Clone> PrecedenceGraph<N, P> {
+    /// Graph with only the edges which are not implied by other edges (the Hasse diagram).
+    pub fn transitive_reduction(&self) -> Self {
+        let mut graph = self.clone();
+
+        // Removed edges are implied by the remaining ones, so cached reachability stays valid.
+        for (outcome, income) in self.redundant_edge_indices() {
+            graph.nodes[outcome].succ.retain(|&succ| succ != income);
+            graph.nodes[income].pred.retain(|&pred| pred != outcome);
+        }
+
+        graph
+    }
+
+    /// Graph with an edge from every node to each node it reaches.
+    pub fn transitive_closure(&self) -> Self {
+        let mut graph = self.clone();
+        let reachability = self.reachability();
+
+        // Added edges are already implied, so cached reachability stays valid.
+        for (index, node) in graph.nodes.iter_mut().enumerate() {
+            node.succ = reachability.descendants(index).iter().collect();
+            node.pred = reachability.ancestors(index).iter().collect();
+        }
+
+        graph
+    }
+}
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    /// Edges implied by other edges.
+    pub fn redundant_edges(&self) -> Vec<(N, N)> {
+        self.redundant_edge_indices()
+            .into_iter()
+            .map(|(outcome, income)| {
+                (
+                    self.nodes[outcome].val.clone(),
+                    self.nodes[income].val.clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<N, P> PrecedenceGraph<N, P> {
+    /// Edges whose income is also reachable through another successor of the outcome.
+    pub(crate) fn redundant_edge_indices(&self) -> Vec<(usize, usize)> {
+        let reachability = self.reachability();
+
+        self.nodes
+            .iter()
+            .enumerate()
+            .flat_map(|(outcome, node)| {
+                node.succ
+                    .iter()
+                    .filter(move |&&income| {
+                        node.succ
+                            .iter()
+                            .any(|&other| other != income && reachability.reaches(other, income))
+                    })
+                    .map(move |&income| (outcome, income))
+            })
+            .collect()
+    }
+}
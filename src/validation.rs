@@ -0,0 +1,229 @@
+use std::{collections::HashSet, hash::Hash};
+
+use crate::precedence_graph::{PrecedenceGraph, PrecedenceGraphError};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ValidationWarning<N = u128> {
+    /// The same edge is listed more than once.
+    DuplicateEdge { outcome: N, income: N },
+    /// The edge is implied by a longer path between the same nodes.
+    RedundantEdge { outcome: N, income: N },
+}
+
+/// Every problem found in a graph description, in input order where there is one.
+#[derive(Debug)]
+pub struct ValidationReport<N = u128> {
+    pub errors: Vec<PrecedenceGraphError<N>>,
+    pub warnings: Vec<ValidationWarning<N>>,
+}
+
+/// Graph with the warnings of its validation, or the report with its errors.
+pub type ValidatedGraph<N = u128, P = ()> =
+    Result<(PrecedenceGraph<N, P>, ValidationReport<N>), ValidationReport<N>>;
+
+impl<N> ValidationReport<N> {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl<N: Hash + Eq + Clone> PrecedenceGraph<N> {
+    /// Checks the nodes and edges without stopping on the first problem.
+    ///
+    /// Redundant edges are only reported for graphs without errors.
+    pub fn validate(
+        nodes: impl IntoIterator<Item = N>,
+        edges: impl IntoIterator<Item = (N, N)>,
+    ) -> ValidationReport<N> {
+        Self::build_with_report(nodes.into_iter().map(|node| (node, ())), edges).1
+    }
+
+    /// Builds the graph if the report has no errors, returning its warnings alongside.
+    pub fn new_with_report(
+        nodes: impl IntoIterator<Item = N>,
+        edges: impl IntoIterator<Item = (N, N)>,
+    ) -> ValidatedGraph<N> {
+        Self::try_build_with_report(nodes.into_iter().map(|node| (node, ())), edges)
+    }
+}
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    pub(crate) fn try_build_with_report(
+        nodes: impl IntoIterator<Item = (N, P)>,
+        edges: impl IntoIterator<Item = (N, N)>,
+    ) -> ValidatedGraph<N, P> {
+        let (graph, report) = Self::build_with_report(nodes, edges);
+
+        if report.is_valid() {
+            Ok((graph, report))
+        } else {
+            Err(report)
+        }
+    }
+
+    fn build_with_report(
+        nodes: impl IntoIterator<Item = (N, P)>,
+        edges: impl IntoIterator<Item = (N, N)>,
+    ) -> (Self, ValidationReport<N>) {
+        let mut graph = Self::default();
+        let mut report = ValidationReport {
+            errors: vec![],
+            warnings: vec![],
+        };
+
+        for (node, payload) in nodes {
+            graph.add_node_with_payload(node, payload);
+        }
+
+        let mut seen = HashSet::new();
+
+        for (outcome, income) in edges {
+            let (out_index, in_index) = match (
+                graph.indices.get(&outcome).copied(),
+                graph.indices.get(&income).copied(),
+            ) {
+                (Some(out_index), Some(in_index)) => (out_index, in_index),
+                (out_index, in_index) => {
+                    if out_index.is_none() {
+                        report
+                            .errors
+                            .push(PrecedenceGraphError::EdgeContainsNonexistentNode {
+                                node: outcome,
+                            });
+                    }
+                    if in_index.is_none() {
+                        report
+                            .errors
+                            .push(PrecedenceGraphError::EdgeContainsNonexistentNode {
+                                node: income,
+                            });
+                    }
+                    continue;
+                }
+            };
+
+            if out_index == in_index {
+                report
+                    .errors
+                    .push(PrecedenceGraphError::SelfLoop { node: outcome });
+                continue;
+            }
+
+            if !seen.insert((out_index, in_index)) {
+                report
+                    .warnings
+                    .push(ValidationWarning::DuplicateEdge { outcome, income });
+                continue;
+            }
+
+            graph.push_edge(out_index, in_index);
+        }
+
+        let cycles = graph.cycles();
+
+        for cycle in &cycles {
+            report.errors.push(PrecedenceGraphError::CycleDetected {
+                cycle: graph.values(cycle.iter().copied()),
+            });
+        }
+
+        if report.is_valid() {
+            for (outcome, income) in graph.redundant_edge_indices() {
+                report.warnings.push(ValidationWarning::RedundantEdge {
+                    outcome: graph.nodes[outcome].val.clone(),
+                    income: graph.nodes[income].val.clone(),
+                });
+            }
+        }
+
+        (graph, report)
+    }
+
+    /// Returns one cycle for every strongly connected component with more than one node.
+    fn cycles(&self) -> Vec<Vec<usize>> {
+        self.strongly_connected_components()
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|component| {
+                let start = component[0];
+                let next = *self.nodes[start]
+                    .succ
+                    .iter()
+                    .find(|succ| component.contains(succ))
+                    .expect("component should contain successor of its node");
+
+                let mut cycle = vec![start];
+                cycle.extend(
+                    self.find_path(next, start)
+                        .expect("component nodes should reach each other"),
+                );
+                cycle.pop();
+
+                cycle
+            })
+            .collect()
+    }
+
+    fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let n = self.nodes.len();
+
+        let mut visited = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+
+        for start in 0..n {
+            if visited[start] {
+                continue;
+            }
+
+            visited[start] = true;
+            let mut stack = vec![(start, 0)];
+
+            while let Some((node, next)) = stack.last_mut() {
+                let node = *node;
+
+                match self.nodes[node].succ.get(*next) {
+                    Some(&succ) => {
+                        *next += 1;
+
+                        if !visited[succ] {
+                            visited[succ] = true;
+                            stack.push((succ, 0));
+                        }
+                    }
+                    None => {
+                        order.push(node);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        let mut component = vec![None; n];
+        let mut components = vec![];
+
+        for &start in order.iter().rev() {
+            if component[start].is_some() {
+                continue;
+            }
+
+            let mut members = vec![];
+            let mut stack = vec![start];
+            component[start] = Some(components.len());
+
+            while let Some(node) = stack.pop() {
+                members.push(node);
+
+                for &pred in &self.nodes[node].pred {
+                    if component[pred].is_none() {
+                        component[pred] = Some(components.len());
+                        stack.push(pred);
+                    }
+                }
+            }
+
+            components.push(members);
+        }
+
+        components
+    }
+}
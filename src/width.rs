@@ -0,0 +1,118 @@
+use std::{collections::VecDeque, hash::Hash};
+
+use crate::precedence_graph::PrecedenceGraph;
+
+/// Maximum matching in the bipartite graph with an edge from every node to each node it reaches.
+struct ChainMatching {
+    /// Node following each node in its chain.
+    next: Vec<Option<usize>>,
+    /// Node preceding each node in its chain.
+    prev: Vec<Option<usize>>,
+}
+
+impl<N: Hash + Eq + Clone, P> PrecedenceGraph<N, P> {
+    /// Size of the largest set of pairwise incomparable nodes.
+    pub fn width(&self) -> usize {
+        let matching = self.chain_matching();
+
+        matching.prev.iter().filter(|prev| prev.is_none()).count()
+    }
+
+    /// Largest set of pairwise incomparable nodes.
+    pub fn maximum_antichain(&self) -> Vec<N> {
+        let reachability = self.reachability();
+        let matching = self.chain_matching();
+        let n = self.nodes.len();
+
+        // Alternating search from unmatched nodes, the antichain is read off the König cover.
+        let mut outcome_visited = vec![false; n];
+        let mut income_visited = vec![false; n];
+        let mut queue: VecDeque<usize> = (0..n)
+            .filter(|&node| matching.next[node].is_none())
+            .collect();
+
+        for &node in &queue {
+            outcome_visited[node] = true;
+        }
+
+        while let Some(node) = queue.pop_front() {
+            for desc in reachability.descendants(node).iter() {
+                if income_visited[desc] {
+                    continue;
+                }
+
+                income_visited[desc] = true;
+
+                if let Some(prev) = matching.prev[desc] {
+                    if !outcome_visited[prev] {
+                        outcome_visited[prev] = true;
+                        queue.push_back(prev);
+                    }
+                }
+            }
+        }
+
+        self.values((0..n).filter(|&node| outcome_visited[node] && !income_visited[node]))
+    }
+
+    /// Fewest chains covering every node, each chain listed in precedence order.
+    pub fn minimum_chain_partition(&self) -> Vec<Vec<N>> {
+        let matching = self.chain_matching();
+
+        (0..self.nodes.len())
+            .filter(|&node| matching.prev[node].is_none())
+            .map(|start| {
+                self.values(std::iter::successors(Some(start), |&node| {
+                    matching.next[node]
+                }))
+            })
+            .collect()
+    }
+
+    fn chain_matching(&self) -> ChainMatching {
+        let reachability = self.reachability();
+        let n = self.nodes.len();
+
+        let mut matching = ChainMatching {
+            next: vec![None; n],
+            prev: vec![None; n],
+        };
+
+        for start in 0..n {
+            // Breadth-first search for an augmenting path, `from` keeps the node each income was reached from.
+            let mut from = vec![None; n];
+            let mut queue = VecDeque::from([start]);
+            let mut end = None;
+
+            'search: while let Some(node) = queue.pop_front() {
+                for desc in reachability.descendants(node).iter() {
+                    if from[desc].is_some() {
+                        continue;
+                    }
+
+                    from[desc] = Some(node);
+
+                    match matching.prev[desc] {
+                        Some(prev) => queue.push_back(prev),
+                        None => {
+                            end = Some(desc);
+                            break 'search;
+                        }
+                    }
+                }
+            }
+
+            let mut income = end;
+
+            while let Some(current) = income {
+                let node = from[current].expect("augmenting path node should have its origin");
+
+                income = matching.next[node];
+                matching.next[node] = Some(current);
+                matching.prev[current] = Some(node);
+            }
+        }
+
+        matching
+    }
+}